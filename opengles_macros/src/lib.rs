@@ -0,0 +1,124 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `#[derive(Vertex)]`, which implements `opengles::vertex::Vertex` for a `#[repr(C)]` struct.
+//!
+//! Every field becomes an attribute of the same name. Fields must implement
+//! `opengles::vertex::VertexField`, and fields marked `#[normalized]` have their fixed-point
+//! components normalized.
+
+#![crate_name = "opengles_macros"]
+#![crate_type = "dylib"]
+
+#![feature(plugin_registrar, quote)]
+
+extern crate rustc;
+extern crate syntax;
+
+use rustc::plugin::Registry;
+use syntax::ast::{Item, ItemStruct, MetaItem, MetaList, MetaWord, NamedField, Stmt};
+use syntax::codemap::Span;
+use syntax::ext::base::{Decorator, ExtCtxt};
+use syntax::ext::build::AstBuilder;
+use syntax::parse::token;
+use syntax::ptr::P;
+
+#[plugin_registrar]
+pub fn plugin_registrar(reg: &mut Registry) {
+    // `#[derive(Foo)]` looks for a `derive_Foo` decorator, and `#[deriving(Foo)]` for a
+    // `deriving_Foo` one.
+    reg.register_syntax_extension(token::intern("derive_Vertex"),
+                                  Decorator(box expand_derive_vertex));
+    reg.register_syntax_extension(token::intern("deriving_Vertex"),
+                                  Decorator(box expand_derive_vertex));
+}
+
+fn expand_derive_vertex(cx: &mut ExtCtxt, span: Span, _: &MetaItem, item: &Item,
+                        push: |P<Item>|) {
+    let struct_def = match item.node {
+        ItemStruct(ref struct_def, ref generics) => {
+            if generics.is_parameterized() {
+                cx.span_err(span, "`derive(Vertex)` cannot be used on generic structs");
+                return
+            }
+            struct_def
+        }
+        _ => {
+            cx.span_err(span, "`derive(Vertex)` can only be used on structs");
+            return
+        }
+    };
+
+    if !is_repr_c(item) {
+        cx.span_err(span, "`derive(Vertex)` requires `#[repr(C)]` so that field offsets are stable");
+        return
+    }
+    if struct_def.fields.is_empty() {
+        cx.span_err(span, "`derive(Vertex)` needs at least one field");
+        return
+    }
+
+    let name = item.ident;
+    let mut stmts: Vec<P<Stmt>> = Vec::new();
+    for field in struct_def.fields.iter() {
+        let field_name = match field.node.kind {
+            NamedField(ident, _) => ident,
+            _ => {
+                cx.span_err(field.span, "`derive(Vertex)` cannot be used on tuple structs");
+                return
+            }
+        };
+        let attribute_name = cx.expr_str(field.span, token::get_ident(field_name));
+        let field_ty = field.node.ty.clone();
+        let normalized = field.node.attrs.iter().any(|attr| attr.check_name("normalized"));
+        let normalized = cx.expr_bool(field.span, normalized);
+        stmts.push(quote_stmt!(cx,
+            attributes.push(::opengles::vertex::VertexAttribute::new::<$field_ty>(
+                $attribute_name,
+                $normalized,
+                &vertex.$field_name as *const _ as uint - base));
+        ));
+    }
+
+    let layout = quote_item!(cx,
+        impl ::opengles::vertex::Vertex for $name {
+            fn layout(_: Option<$name>) -> ::opengles::vertex::VertexLayout {
+                // The offsets are the addresses of the fields of a real vertex relative to its
+                // own. Every field is a `VertexField`, made of integers and floats, so all zero
+                // bits are a valid vertex.
+                let vertex: $name = unsafe { ::std::mem::zeroed() };
+                let base = &vertex as *const $name as uint;
+                let mut attributes = Vec::new();
+                $stmts
+                ::opengles::vertex::VertexLayout {
+                    stride: ::std::mem::size_of::<$name>(),
+                    attributes: attributes,
+                }
+            }
+        }
+    );
+    push(layout.unwrap());
+}
+
+fn is_repr_c(item: &Item) -> bool {
+    item.attrs.iter().any(|attr| {
+        if !attr.check_name("repr") {
+            return false
+        }
+        match attr.node.value.node {
+            MetaList(_, ref items) => items.iter().any(|item| {
+                match item.node {
+                    MetaWord(ref word) => word.get() == "C",
+                    _ => false,
+                }
+            }),
+            _ => false,
+        }
+    })
+}
@@ -0,0 +1,513 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Versions of the `gl2` wrappers that return the first error GL raised during the call, if any.
+//! The error queue is drained before the call, discarding errors left by earlier unchecked
+//! calls, and again after it.
+//!
+//! These do not mix with the `debug_gl` feature. They suspend its check for their own call, so
+//! the error reaches the caller rather than the error hook, but an error raised by an unchecked
+//! call under `debug_gl` is taken off the queue by the check and never seen here.
+
+use error::{GlError, drain_errors};
+use gl2::{ActiveInfo, Gl, GLbitfield, GLclampf, GLenum, GLfloat, GLint, GLsizei, GLuint};
+use gl2::{AttribComponent, IndexType, VertexAttribState};
+#[cfg(target_os="android")]
+use gl2::GLeglImageOES;
+use libc::c_int;
+use pixels::PixelStore;
+
+macro_rules! checked {
+    ($($(#[$attr:meta])* fn $name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {
+        $($(#[$attr])*
+        pub fn $name(gl: &Gl $(, $arg: $ty)*) -> Result<$ret, GlError> {
+            let _suspended = gl.suspend_debug_checks();
+            discard_errors(gl);
+            let result = gl.$name($($arg),*);
+            try!(drain_errors(gl));
+            Ok(result)
+        })*
+    }
+}
+
+// Like `checked!`, for wrappers that reject some arguments themselves. A rejection is reported
+// as the error GL would have raised.
+macro_rules! checked_result {
+    ($($(#[$attr:meta])* fn $name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {
+        $($(#[$attr])*
+        pub fn $name(gl: &Gl $(, $arg: $ty)*) -> Result<$ret, GlError> {
+            let _suspended = gl.suspend_debug_checks();
+            discard_errors(gl);
+            let result = gl.$name($($arg),*);
+            try!(drain_errors(gl));
+            result.map_err(|error| error.to_gl_error())
+        })*
+    }
+}
+
+// Discards the errors left queued by earlier unchecked calls, so that they are not reported
+// as the error of this call.
+fn discard_errors(gl: &Gl) {
+    let _ = drain_errors(gl);
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn buffer_data<T>(gl: &Gl, target: GLenum, data: &[T], usage: GLenum) -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    gl.buffer_data(target, data, usage);
+    drain_errors(gl)
+}
+
+#[cfg(target_os="android")]
+pub fn buffer_data(gl: &Gl, target: GLenum, data: &[f32], usage: GLenum) -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    gl.buffer_data(target, data, usage);
+    drain_errors(gl)
+}
+
+pub fn buffer_sub_data<T>(gl: &Gl, target: GLenum, element_offset_index: uint, data: &[T])
+                          -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    gl.buffer_sub_data(target, element_offset_index, data);
+    drain_errors(gl)
+}
+
+/// Like `Gl::compressed_tex_image_2d`, but reports rejected arguments as the error GL would have
+/// raised.
+pub fn compressed_tex_image_2d(gl: &Gl,
+                               target: GLenum,
+                               level: GLint,
+                               internal_format: GLenum,
+                               width: GLsizei,
+                               height: GLsizei,
+                               border: GLint,
+                               data: &[u8])
+                               -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.compressed_tex_image_2d(target, level, internal_format, width, height, border,
+                                            data);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+/// Like `Gl::compressed_tex_sub_image_2d`, but reports rejected arguments as the error GL would
+/// have raised.
+pub fn compressed_tex_sub_image_2d(gl: &Gl,
+                                   target: GLenum,
+                                   level: GLint,
+                                   xoffset: GLint,
+                                   yoffset: GLint,
+                                   width: GLsizei,
+                                   height: GLsizei,
+                                   format: GLenum,
+                                   data: &[u8])
+                                   -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.compressed_tex_sub_image_2d(target, level, xoffset, yoffset, width, height,
+                                                format, data);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+/// Like `Gl::draw_elements_indexed`, but reports a rejected count as the error GL would have
+/// raised.
+pub fn draw_elements_indexed<T: IndexType>(gl: &Gl, mode: GLenum, count: GLsizei, indices: &[T])
+                                           -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.draw_elements_indexed(mode, count, indices);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+/// Like `Gl::draw_elements_offset`, but reports a rejected count or offset as the error GL
+/// would have raised.
+pub fn draw_elements_offset<T: IndexType>(gl: &Gl, mode: GLenum, count: GLsizei, offset: uint)
+                                          -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.draw_elements_offset::<T>(mode, count, offset);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+/// Like `Gl::draw_elements_instanced_indexed`, but reports a rejected count as the error GL would
+/// have raised.
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn draw_elements_instanced_indexed<T: IndexType>(gl: &Gl,
+                                                     mode: GLenum,
+                                                     count: GLsizei,
+                                                     indices: &[T],
+                                                     primcount: GLsizei)
+                                                     -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.draw_elements_instanced_indexed(mode, count, indices, primcount);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+/// Like `Gl::draw_elements_instanced_offset`, but reports a rejected count or offset as the
+/// error GL would have raised.
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn draw_elements_instanced_offset<T: IndexType>(gl: &Gl,
+                                                    mode: GLenum,
+                                                    count: GLsizei,
+                                                    offset: uint,
+                                                    primcount: GLsizei)
+                                                    -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.draw_elements_instanced_offset::<T>(mode, count, offset, primcount);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+/// Like `Gl::read_pixels`, but reports rejected arguments as the error GL would have raised.
+pub fn read_pixels(gl: &Gl,
+                   x: GLint,
+                   y: GLint,
+                   width: GLsizei,
+                   height: GLsizei,
+                   format: GLenum,
+                   pixel_type: GLenum)
+                   -> Result<Vec<u8>, GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.read_pixels(x, y, width, height, format, pixel_type);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+/// Like `Gl::read_pixels_into`, but reports rejected arguments as the error GL would have raised.
+pub fn read_pixels_into(gl: &Gl,
+                        x: GLint,
+                        y: GLint,
+                        width: GLsizei,
+                        height: GLsizei,
+                        format: GLenum,
+                        pixel_type: GLenum,
+                        pixels: &mut [u8])
+                        -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.read_pixels_into(x, y, width, height, format, pixel_type, pixels);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+/// Like `Gl::tex_image_2d`, but reports rejected arguments as the error GL would have raised.
+pub fn tex_image_2d(gl: &Gl,
+                    target: GLenum,
+                    level: GLint,
+                    internal_format: GLint,
+                    width: GLsizei,
+                    height: GLsizei,
+                    border: GLint,
+                    format: GLenum,
+                    ty: GLenum,
+                    opt_data: Option<&[u8]>)
+                    -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.tex_image_2d(target, level, internal_format, width, height, border, format, ty,
+                                 opt_data);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+/// Like `Gl::tex_parameter_fv`, but reports a short `params` as `INVALID_VALUE`.
+pub fn tex_parameter_fv(gl: &Gl, target: GLenum, pname: GLenum, params: &[GLfloat])
+                        -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.tex_parameter_fv(target, pname, params);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+/// Like `Gl::tex_parameter_iv`, but reports a short `params` as `INVALID_VALUE`.
+pub fn tex_parameter_iv(gl: &Gl, target: GLenum, pname: GLenum, params: &[GLint])
+                        -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.tex_parameter_iv(target, pname, params);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+/// Like `Gl::tex_sub_image_2d`, but reports rejected arguments as the error GL would have raised.
+pub fn tex_sub_image_2d(gl: &Gl,
+                        target: GLenum,
+                        level: GLint,
+                        xoffset: GLint,
+                        yoffset: GLint,
+                        width: GLsizei,
+                        height: GLsizei,
+                        format: GLenum,
+                        ty: GLenum,
+                        opt_data: Option<&[u8]>)
+                        -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.tex_sub_image_2d(target, level, xoffset, yoffset, width, height, format, ty,
+                                     opt_data);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+pub fn vertex_attrib_pointer<T: AttribComponent>(gl: &Gl,
+                                                 index: GLuint,
+                                                 size: GLint,
+                                                 normalized: bool,
+                                                 stride: GLsizei,
+                                                 offset: uint) -> Result<(), GlError> {
+    let _suspended = gl.suspend_debug_checks();
+    discard_errors(gl);
+    let result = gl.vertex_attrib_pointer::<T>(index, size, normalized, stride, offset);
+    try!(drain_errors(gl));
+    result.map_err(|error| error.to_gl_error())
+}
+
+checked! {
+    fn active_texture(texture: GLenum) -> ();
+    fn attach_shader(program: GLuint, shader: GLuint) -> ();
+    fn bind_attrib_location(program: GLuint, index: GLuint, name: &str) -> ();
+    fn bind_buffer(target: GLenum, buffer: GLuint) -> ();
+    fn bind_framebuffer(target: GLenum, framebuffer: GLuint) -> ();
+    fn bind_renderbuffer(target: GLenum, renderbuffer: GLuint) -> ();
+    fn bind_texture(target: GLenum, texture: GLuint) -> ();
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    fn bind_vertex_array(array: GLuint) -> ();
+    fn blend_color(red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf) -> ();
+    fn blend_equation(mode: GLenum) -> ();
+    fn blend_equation_separate(mode_rgb: GLenum, mode_alpha: GLenum) -> ();
+    fn blend_func(sfactor: GLenum, dfactor: GLenum) -> ();
+    fn blend_func_separate(src_rgb: GLenum,
+                           dst_rgb: GLenum,
+                           src_alpha: GLenum,
+                           dst_alpha: GLenum) -> ();
+    fn check_framebuffer_status(target: GLenum) -> GLenum;
+    fn clear(mask: GLbitfield) -> ();
+    fn clear_color(red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf) -> ();
+    fn clear_stencil(s: GLint) -> ();
+    fn compile_shader(shader: GLuint) -> ();
+    fn copy_tex_image_2d(target: GLenum,
+                         level: GLint,
+                         internal_format: GLenum,
+                         x: GLint,
+                         y: GLint,
+                         width: GLsizei,
+                         height: GLsizei,
+                         border: GLint) -> ();
+    fn copy_tex_sub_image_2d(target: GLenum,
+                             level: GLint,
+                             xoffset: GLint,
+                             yoffset: GLint,
+                             x: GLint,
+                             y: GLint,
+                             width: GLsizei,
+                             height: GLsizei) -> ();
+    fn create_program() -> GLuint;
+    fn create_shader(shader_type: GLenum) -> GLuint;
+    fn cull_face(mode: GLenum) -> ();
+    fn delete_buffers(buffers: &[GLuint]) -> ();
+    fn delete_frame_buffers(frame_buffers: &[GLuint]) -> ();
+    fn delete_program(program: GLuint) -> ();
+    fn delete_render_buffers(render_buffers: &[GLuint]) -> ();
+    fn delete_shader(shader: GLuint) -> ();
+    fn delete_textures(textures: &[GLuint]) -> ();
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    fn delete_vertex_arrays(arrays: &[GLuint]) -> ();
+    fn depth_func(func: GLenum) -> ();
+    fn depth_mask(flag: bool) -> ();
+    fn detach_shader(program: GLuint, shader: GLuint) -> ();
+    fn draw_arrays(mode: GLenum, first: GLint, count: GLsizei) -> ();
+    fn draw_elements(mode: GLenum,
+                     count: GLsizei,
+                     element_type: GLenum,
+                     indices: Option<&[u8]>) -> ();
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    fn draw_arrays_instanced(mode: GLenum, first: GLint, count: GLsizei, primcount: GLsizei) -> ();
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    fn draw_elements_instanced(mode: GLenum,
+                               count: GLsizei,
+                               element_type: GLenum,
+                               indices: Option<&[u8]>,
+                               primcount: GLsizei) -> ();
+    fn enable(cap: GLenum) -> ();
+    fn disable(cap: GLenum) -> ();
+    fn enable_vertex_attrib_array(index: GLuint) -> ();
+    fn disable_vertex_attrib_array(index: GLuint) -> ();
+    fn finish() -> ();
+    fn flush() -> ();
+    fn framebuffer_renderbuffer(target: GLenum,
+                                attachment: GLenum,
+                                renderbuffertarget: GLenum,
+                                renderbuffer: GLuint) -> ();
+    fn framebuffer_texture_2d(target: GLenum,
+                              attachment: GLenum,
+                              textarget: GLenum,
+                              texture: GLuint,
+                              level: GLint) -> ();
+    fn front_face(mode: GLenum) -> ();
+    fn gen_buffers(n: GLsizei) -> Vec<GLuint>;
+    fn gen_framebuffers(n: GLsizei) -> Vec<GLuint>;
+    fn gen_renderbuffers(n: GLsizei) -> Vec<GLuint>;
+    fn gen_textures(n: GLsizei) -> Vec<GLuint>;
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    fn gen_vertex_arrays(n: GLsizei) -> Vec<GLuint>;
+    fn generate_mipmap(target: GLenum) -> ();
+    fn get_active_attrib(program: GLuint, index: GLuint) -> ActiveInfo;
+    fn get_active_uniform(program: GLuint, index: GLuint) -> ActiveInfo;
+    fn get_attached_shaders(program: GLuint) -> Vec<GLuint>;
+    fn get_attrib_location(program: GLuint, name: &str) -> c_int;
+    fn get_compressed_texture_formats() -> Vec<GLenum>;
+    fn get_framebuffer_attachment_parameter_iv(target: GLenum, attachment: GLenum, pname: GLenum)
+                                               -> GLint;
+    fn get_integer_v(pname: GLenum, result: &mut [GLint]) -> ();
+    fn get_program_info_log(program: GLuint) -> String;
+    fn get_program_iv(program: GLuint, pname: GLenum) -> GLint;
+    fn get_renderbuffer_parameter_iv(target: GLenum, pname: GLenum) -> GLint;
+    fn get_shader_info_log(shader: GLuint) -> String;
+    fn get_string(which: GLenum) -> String;
+    fn get_tex_parameter_fv(target: GLenum, pname: GLenum) -> GLfloat;
+    fn get_tex_parameter_iv(target: GLenum, pname: GLenum) -> GLint;
+    fn get_shader_iv(shader: GLuint, pname: GLenum) -> GLint;
+    fn get_uniform_fv(program: GLuint, location: GLint, result: &mut [GLfloat]) -> ();
+    fn get_uniform_iv(program: GLuint, location: GLint, result: &mut [GLint]) -> ();
+    fn get_uniform_location(program: GLuint, name: &str) -> c_int;
+    fn get_vertex_attrib_fv(index: GLuint, pname: GLenum, result: &mut [GLfloat]) -> ();
+    fn get_vertex_attrib_iv(index: GLuint, pname: GLenum, result: &mut [GLint]) -> ();
+    fn get_vertex_attrib_pointer_v(index: GLuint, pname: GLenum) -> uint;
+    fn get_vertex_attrib_state(index: GLuint) -> VertexAttribState;
+    fn hint(target: GLenum, mode: GLenum) -> ();
+    fn is_buffer(buffer: GLuint) -> bool;
+    fn is_enabled(cap: GLenum) -> bool;
+    fn is_framebuffer(framebuffer: GLuint) -> bool;
+    fn is_program(program: GLuint) -> bool;
+    fn is_renderbuffer(renderbuffer: GLuint) -> bool;
+    fn is_shader(shader: GLuint) -> bool;
+    fn is_texture(texture: GLuint) -> bool;
+    fn line_width(width: GLfloat) -> ();
+    fn link_program(program: GLuint) -> ();
+    fn pixel_store_i(pname: GLenum, param: GLint) -> ();
+    #[cfg(not(target_os = "android"))]
+    fn polygon_mode(face: GLenum, mode: GLenum) -> ();
+    fn renderbuffer_storage(target: GLenum, internalformat: GLenum, width: GLsizei, height: GLsizei) -> ();
+    fn scissor(x: GLint, y: GLint, width: GLsizei, height: GLsizei) -> ();
+    fn set_pixel_store(store: &PixelStore) -> ();
+    fn shader_source(shader: GLuint, strings: &[&[u8]]) -> ();
+    fn stencil_func(func: GLenum, reference: GLint, mask: GLuint) -> ();
+    fn stencil_func_separate(face: GLenum, func: GLenum, reference: GLint, mask: GLuint) -> ();
+    fn stencil_mask(mask: GLuint) -> ();
+    fn stencil_mask_separate(face: GLenum, mask: GLuint) -> ();
+    fn stencil_op(sfail: GLenum, dpfail: GLenum, dppass: GLenum) -> ();
+    fn stencil_op_separate(face: GLenum, sfail: GLenum, dpfail: GLenum, dppass: GLenum) -> ();
+    fn tex_parameter_f(target: GLenum, pname: GLenum, param: GLfloat) -> ();
+    fn tex_parameter_i(target: GLenum, pname: GLenum, param: GLint) -> ();
+    fn uniform_1f(location: GLint, x: GLfloat) -> ();
+    fn uniform_1i(location: GLint, x: GLint) -> ();
+    fn uniform_2f(location: GLint, x: GLfloat, y: GLfloat) -> ();
+    fn uniform_2i(location: GLint, x: GLint, y: GLint) -> ();
+    fn uniform_3f(location: GLint, x: GLfloat, y: GLfloat, z: GLfloat) -> ();
+    fn uniform_3i(location: GLint, x: GLint, y: GLint, z: GLint) -> ();
+    fn uniform_4f(location: GLint, x: GLfloat, y: GLfloat, z: GLfloat, w: GLfloat) -> ();
+    fn uniform_4i(location: GLint, x: GLint, y: GLint, z: GLint, w: GLint) -> ();
+    fn use_program(program: GLuint) -> ();
+    fn validate_program(program: GLuint) -> ();
+    fn vertex_attrib_1f(index: GLuint, x: GLfloat) -> ();
+    fn vertex_attrib_1fv(index: GLuint, values: &[GLfloat, ..1]) -> ();
+    fn vertex_attrib_2f(index: GLuint, x: GLfloat, y: GLfloat) -> ();
+    fn vertex_attrib_2fv(index: GLuint, values: &[GLfloat, ..2]) -> ();
+    fn vertex_attrib_3f(index: GLuint, x: GLfloat, y: GLfloat, z: GLfloat) -> ();
+    fn vertex_attrib_3fv(index: GLuint, values: &[GLfloat, ..3]) -> ();
+    fn vertex_attrib_4f(index: GLuint, x: GLfloat, y: GLfloat, z: GLfloat, w: GLfloat) -> ();
+    fn vertex_attrib_4fv(index: GLuint, values: &[GLfloat, ..4]) -> ();
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    fn vertex_attrib_divisor(index: GLuint, divisor: GLuint) -> ();
+    fn viewport(x: GLint, y: GLint, width: GLsizei, height: GLsizei) -> ();
+    #[cfg(target_os="android")]
+    fn egl_image_target_texture2d_oes(target: GLenum, image: GLeglImageOES) -> ();
+    #[cfg(target_os="android")]
+    fn egl_image_target_renderbuffer_storage_oes(target: GLenum, image: GLeglImageOES) -> ();
+}
+
+checked_result! {
+    fn uniform_1fv(location: GLint, values: &[f32]) -> ();
+    fn uniform_1iv(location: GLint, values: &[i32]) -> ();
+    fn uniform_2fv(location: GLint, values: &[f32]) -> ();
+    fn uniform_2iv(location: GLint, values: &[i32]) -> ();
+    fn uniform_3fv(location: GLint, values: &[f32]) -> ();
+    fn uniform_3iv(location: GLint, values: &[i32]) -> ();
+    fn uniform_4fv(location: GLint, values: &[f32]) -> ();
+    fn uniform_4iv(location: GLint, values: &[i32]) -> ();
+    fn uniform_matrix_2fv(location: GLint, transpose: bool, value: &[f32]) -> ();
+    fn uniform_matrix_3fv(location: GLint, transpose: bool, value: &[f32]) -> ();
+    fn uniform_matrix_4fv(location: GLint, transpose: bool, value: &[f32]) -> ();
+}
+
+#[cfg(test)]
+mod test {
+    use error::GlError;
+    use gl2::{INVALID_ENUM, NO_ERROR, TEXTURE_2D};
+    use mock;
+
+    #[test]
+    fn returns_the_error_raised_by_the_call() {
+        mock::reset();
+        let gl = mock::load();
+        // The queue is empty before the call and holds the error after it.
+        mock::push_return("glGetError", NO_ERROR as i64);
+        mock::push_return("glGetError", INVALID_ENUM as i64);
+        assert_eq!(super::bind_texture(&gl, TEXTURE_2D, 1), Err(GlError::InvalidEnum));
+        assert_eq!(super::bind_texture(&gl, TEXTURE_2D, 1), Ok(()));
+    }
+
+    #[test]
+    fn ignores_errors_raised_before_the_call() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetError", INVALID_ENUM as i64);
+        mock::push_return("glGetError", NO_ERROR as i64);
+        assert_eq!(super::bind_texture(&gl, TEXTURE_2D, 1), Ok(()));
+    }
+
+    #[cfg(feature = "debug_gl")]
+    #[test]
+    fn suspends_debug_checks() {
+        use gl2;
+
+        fn fail(name: &str, _: &[String], _: gl2::GLenum) {
+            panic!("{} was checked by debug_gl", name);
+        }
+
+        mock::reset();
+        let gl = mock::load();
+        gl2::set_error_hook(Some(fail));
+        mock::push_return("glGetError", NO_ERROR as i64);
+        mock::push_return("glGetError", INVALID_ENUM as i64);
+        let result = super::bind_texture(&gl, TEXTURE_2D, 1);
+        gl2::set_error_hook(None);
+        assert_eq!(result, Err(GlError::InvalidEnum));
+    }
+
+    #[test]
+    fn reports_rejected_uniform_data() {
+        mock::reset();
+        let gl = mock::load();
+        assert_eq!(super::uniform_2fv(&gl, 0, &[1.0, 2.0, 3.0]), Err(GlError::InvalidValue));
+        assert_eq!(super::uniform_2fv(&gl, 0, &[1.0, 2.0]), Ok(()));
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The block layouts of the compressed texture formats, used to check the `imageSize` of
+//! compressed uploads.
+
+use error::PixelError;
+use gl2;
+use gl2::{Gl, GLenum, GLsizei};
+use std::cmp;
+use std::num::Int;
+
+/// How a compressed format divides an image into fixed-size blocks.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct CompressedFormat {
+    pub format: GLenum,
+    /// The width in pixels covered by one block.
+    pub block_width: uint,
+    /// The height in pixels covered by one block.
+    pub block_height: uint,
+    /// The size in bytes of one block.
+    pub block_bytes: uint,
+    /// The fewest blocks stored along each axis, however small the image. Only PVRTC has a
+    /// minimum above 1.
+    pub min_blocks: uint,
+}
+
+impl CompressedFormat {
+    /// Looks up the block layout of `format`, returning `None` for formats not in the table.
+    pub fn from_gl(format: GLenum) -> Option<CompressedFormat> {
+        let (block_width, block_height, block_bytes, min_blocks) = match format {
+            gl2::ETC1_RGB8_OES | gl2::COMPRESSED_R11_EAC | gl2::COMPRESSED_SIGNED_R11_EAC |
+            gl2::COMPRESSED_RGB8_ETC2 | gl2::COMPRESSED_SRGB8_ETC2 |
+            gl2::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2 |
+            gl2::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2 => (4, 4, 8, 1),
+            gl2::COMPRESSED_RG11_EAC | gl2::COMPRESSED_SIGNED_RG11_EAC |
+            gl2::COMPRESSED_RGBA8_ETC2_EAC | gl2::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC => (4, 4, 16, 1),
+
+            gl2::COMPRESSED_RGB_S3TC_DXT1_EXT | gl2::COMPRESSED_RGBA_S3TC_DXT1_EXT => (4, 4, 8, 1),
+            gl2::COMPRESSED_RGBA_S3TC_DXT3_EXT |
+            gl2::COMPRESSED_RGBA_S3TC_DXT5_EXT => (4, 4, 16, 1),
+
+            gl2::COMPRESSED_RGB_PVRTC_4BPPV1_IMG |
+            gl2::COMPRESSED_RGBA_PVRTC_4BPPV1_IMG => (4, 4, 8, 2),
+            gl2::COMPRESSED_RGB_PVRTC_2BPPV1_IMG |
+            gl2::COMPRESSED_RGBA_PVRTC_2BPPV1_IMG => (8, 4, 8, 2),
+
+            gl2::COMPRESSED_RGBA_ASTC_4x4_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_4x4_KHR => (4, 4, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_5x4_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_5x4_KHR => (5, 4, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_5x5_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_5x5_KHR => (5, 5, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_6x5_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_6x5_KHR => (6, 5, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_6x6_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_6x6_KHR => (6, 6, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_8x5_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_8x5_KHR => (8, 5, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_8x6_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_8x6_KHR => (8, 6, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_8x8_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_8x8_KHR => (8, 8, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_10x5_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_10x5_KHR => (10, 5, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_10x6_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_10x6_KHR => (10, 6, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_10x8_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_10x8_KHR => (10, 8, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_10x10_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_10x10_KHR => (10, 10, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_12x10_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_12x10_KHR => (12, 10, 16, 1),
+            gl2::COMPRESSED_RGBA_ASTC_12x12_KHR |
+            gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_12x12_KHR => (12, 12, 16, 1),
+
+            _ => return None,
+        };
+        Some(CompressedFormat {
+            format: format,
+            block_width: block_width,
+            block_height: block_height,
+            block_bytes: block_bytes,
+            min_blocks: min_blocks,
+        })
+    }
+
+    /// Returns the `imageSize` of a `width` by `height` image in this format, or an error if it
+    /// does not fit in a `uint`.
+    pub fn image_size(&self, width: GLsizei, height: GLsizei) -> Result<uint, PixelError> {
+        if width < 0 || height < 0 {
+            return Err(PixelError::InvalidDimensions(width, height))
+        }
+        if width == 0 || height == 0 {
+            return Ok(0)
+        }
+        let blocks_x = cmp::max(blocks(width as uint, self.block_width), self.min_blocks);
+        let blocks_y = cmp::max(blocks(height as uint, self.block_height), self.min_blocks);
+        let size = blocks_x.checked_mul(blocks_y).and_then(|blocks| {
+            blocks.checked_mul(self.block_bytes)
+        });
+        match size {
+            Some(size) => Ok(size),
+            None => Err(PixelError::Overflow(width, height)),
+        }
+    }
+
+    /// Checks that `data` is exactly one `width` by `height` image in this format.
+    pub fn check_image_size(&self, width: GLsizei, height: GLsizei, data: &[u8])
+                            -> Result<(), PixelError> {
+        let expected = try!(self.image_size(width, height));
+        if data.len() != expected {
+            return Err(PixelError::WrongSize {
+                expected: expected,
+                actual: data.len(),
+            })
+        }
+        Ok(())
+    }
+}
+
+/// Whether `gl` lists `format` among its `COMPRESSED_TEXTURE_FORMATS`.
+pub fn is_supported(gl: &Gl, format: GLenum) -> bool {
+    gl.get_compressed_texture_formats().contains(&format)
+}
+
+/// Checks that `data` is exactly one `width` by `height` image in `format`.
+pub fn check_image_size(format: GLenum, width: GLsizei, height: GLsizei, data: &[u8])
+                        -> Result<(), PixelError> {
+    match CompressedFormat::from_gl(format) {
+        Some(format) => format.check_image_size(width, height, data),
+        None => Err(PixelError::UnsupportedFormat(format)),
+    }
+}
+
+fn blocks(pixels: uint, block_size: uint) -> uint {
+    (pixels + block_size - 1) / block_size
+}
+
+#[cfg(test)]
+mod test {
+    use error::PixelError;
+    use gl2;
+    use std::{i32, uint};
+    use super::CompressedFormat;
+
+    fn image_size(format: gl2::GLenum, width: gl2::GLsizei, height: gl2::GLsizei) -> uint {
+        CompressedFormat::from_gl(format).unwrap().image_size(width, height).unwrap()
+    }
+
+    #[test]
+    fn partial_blocks_are_rounded_up() {
+        assert_eq!(image_size(gl2::ETC1_RGB8_OES, 4, 4), 8);
+        assert_eq!(image_size(gl2::ETC1_RGB8_OES, 5, 5), 4 * 8);
+        assert_eq!(image_size(gl2::COMPRESSED_RGBA_S3TC_DXT5_EXT, 8, 1), 2 * 16);
+    }
+
+    #[test]
+    fn pvrtc_stores_at_least_two_blocks_per_axis() {
+        assert_eq!(image_size(gl2::COMPRESSED_RGB_PVRTC_4BPPV1_IMG, 1, 1), 4 * 8);
+        assert_eq!(image_size(gl2::COMPRESSED_RGBA_PVRTC_2BPPV1_IMG, 8, 8), 4 * 8);
+        assert_eq!(image_size(gl2::COMPRESSED_RGBA_PVRTC_2BPPV1_IMG, 32, 16), 4 * 4 * 8);
+    }
+
+    #[test]
+    fn astc_blocks_need_not_be_square() {
+        assert_eq!(image_size(gl2::COMPRESSED_RGBA_ASTC_12x10_KHR, 13, 13), 2 * 2 * 16);
+        assert_eq!(image_size(gl2::COMPRESSED_SRGB8_ALPHA8_ASTC_5x4_KHR, 10, 8), 2 * 2 * 16);
+    }
+
+    #[test]
+    fn empty_images_have_no_blocks() {
+        assert_eq!(image_size(gl2::COMPRESSED_RGB_PVRTC_4BPPV1_IMG, 0, 16), 0);
+    }
+
+    #[test]
+    fn sizes_that_do_not_fit_are_an_error() {
+        let format = CompressedFormat::from_gl(gl2::COMPRESSED_RGBA_ASTC_4x4_KHR).unwrap();
+        let size = format.image_size(i32::MAX, i32::MAX);
+        if uint::BITS == 32 {
+            assert_eq!(size, Err(PixelError::Overflow(i32::MAX, i32::MAX)));
+        } else {
+            assert!(size.is_ok());
+        }
+    }
+
+    #[test]
+    fn unknown_formats_are_not_in_the_table() {
+        assert_eq!(CompressedFormat::from_gl(gl2::RGBA), None);
+        assert_eq!(super::check_image_size(gl2::RGBA, 4, 4, &[]),
+                   Err(PixelError::UnsupportedFormat(gl2::RGBA)));
+    }
+
+    #[test]
+    fn data_must_be_exactly_one_image() {
+        let format = CompressedFormat::from_gl(gl2::ETC1_RGB8_OES).unwrap();
+        assert_eq!(format.check_image_size(4, 4, &[0, ..8]), Ok(()));
+        assert_eq!(format.check_image_size(4, 4, &[0, ..9]), Err(PixelError::WrongSize {
+            expected: 8,
+            actual: 9,
+        }));
+    }
+}
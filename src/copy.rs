@@ -0,0 +1,285 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Bounds-checked copies from the read framebuffer into textures.
+//!
+//! OpenGL ES 2.0 can query neither the size of the default framebuffer nor the size of a texture
+//! level, so the caller passes both; they are usually known from when the surface and texture
+//! were created.
+
+use error::GlError;
+use framebuffer::{Attachment, get_attachment, renderbuffer_storage};
+use gl2;
+use gl2::{Gl, GLenum, GLint, GLsizei};
+use std::error::Error;
+use std::fmt;
+
+/// A rectangle of pixels with its origin at the lower left.
+#[deriving(Clone, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: GLint,
+    pub y: GLint,
+    pub width: GLsizei,
+    pub height: GLsizei,
+}
+
+impl Rect {
+    pub fn new(x: GLint, y: GLint, width: GLsizei, height: GLsizei) -> Rect {
+        Rect {
+            x: x,
+            y: y,
+            width: width,
+            height: height,
+        }
+    }
+
+    /// A rectangle at the origin, such as the bounds of a framebuffer or texture level.
+    pub fn from_size(width: GLsizei, height: GLsizei) -> Rect {
+        Rect::new(0, 0, width, height)
+    }
+
+    /// Whether `other` lies entirely within this rectangle.
+    pub fn contains(&self, other: &Rect) -> bool {
+        // Widened so that rectangles near the limits of `GLint` cannot overflow.
+        other.x >= self.x && other.y >= self.y &&
+            other.x as i64 + other.width as i64 <= self.x as i64 + self.width as i64 &&
+            other.y as i64 + other.height as i64 <= self.y as i64 + self.height as i64
+    }
+}
+
+impl fmt::Show for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{} at ({}, {})", self.width, self.height, self.x, self.y)
+    }
+}
+
+/// A copy from the framebuffer into a texture that a wrapper refused to pass to GL.
+#[deriving(Clone, PartialEq, Eq, Hash)]
+pub enum CopyError {
+    /// The width or height is negative.
+    InvalidDimensions(GLsizei, GLsizei),
+    /// The border is not 0.
+    InvalidBorder(GLint),
+    /// The source rectangle extends past the read framebuffer, whose bounds follow it.
+    SourceOutOfBounds(Rect, Rect),
+    /// The destination rectangle extends past the texture level, whose bounds follow it.
+    DestinationOutOfBounds(Rect, Rect),
+}
+
+impl CopyError {
+    /// Returns the error GL reports for the same arguments. GL leaves the pixels outside the
+    /// framebuffer undefined rather than reporting an out-of-bounds source, so `InvalidValue`,
+    /// the error for the other bounds, stands in for it.
+    pub fn to_gl_error(&self) -> GlError {
+        match *self {
+            CopyError::InvalidDimensions(..) |
+            CopyError::InvalidBorder(_) |
+            CopyError::DestinationOutOfBounds(..) => GlError::InvalidValue,
+            CopyError::SourceOutOfBounds(..) => GlError::InvalidValue,
+        }
+    }
+}
+
+impl fmt::Show for CopyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CopyError::InvalidDimensions(width, height) => {
+                write!(f, "invalid copy size {}x{}", width, height)
+            }
+            CopyError::InvalidBorder(border) => write!(f, "invalid border {}", border),
+            CopyError::SourceOutOfBounds(ref source, ref bounds) => {
+                write!(f, "source {} extends past the framebuffer {}", source, bounds)
+            }
+            CopyError::DestinationOutOfBounds(ref destination, ref bounds) => {
+                write!(f, "destination {} extends past the texture level {}", destination, bounds)
+            }
+        }
+    }
+}
+
+impl Error for CopyError {
+    fn description(&self) -> &str {
+        match *self {
+            CopyError::InvalidDimensions(..) => "negative width or height",
+            CopyError::InvalidBorder(_) => "nonzero border",
+            CopyError::SourceOutOfBounds(..) => "source rectangle outside the framebuffer",
+            CopyError::DestinationOutOfBounds(..) => "destination rectangle outside the texture",
+        }
+    }
+}
+
+/// Specifies level `level` of the texture bound to `target` from the `source` rectangle of the
+/// read framebuffer.
+///
+/// The source is checked against the size of the color renderbuffer of the bound framebuffer.
+/// OpenGL ES 2.0 cannot report the size of the default framebuffer or of a texture level, so
+/// when either is bound the caller's `framebuffer_width` and `framebuffer_height` are used.
+pub fn copy_tex_image_2d(gl: &Gl,
+                         target: GLenum,
+                         level: GLint,
+                         internal_format: GLenum,
+                         source: Rect,
+                         framebuffer_width: GLsizei,
+                         framebuffer_height: GLsizei,
+                         border: GLint)
+                         -> Result<(), CopyError> {
+    if border != 0 {
+        return Err(CopyError::InvalidBorder(border))
+    }
+    try!(check_source(gl, &source, framebuffer_width, framebuffer_height));
+    gl.copy_tex_image_2d(target, level, internal_format, source.x, source.y, source.width,
+                         source.height, border);
+    Ok(())
+}
+
+/// Copies the `source` rectangle of the read framebuffer to `xoffset`, `yoffset` in level
+/// `level` of the texture bound to `target`, which is `level_width` by `level_height`. The
+/// source is checked as in `copy_tex_image_2d`.
+pub fn copy_tex_sub_image_2d(gl: &Gl,
+                             target: GLenum,
+                             level: GLint,
+                             xoffset: GLint,
+                             yoffset: GLint,
+                             source: Rect,
+                             framebuffer_width: GLsizei,
+                             framebuffer_height: GLsizei,
+                             level_width: GLsizei,
+                             level_height: GLsizei)
+                             -> Result<(), CopyError> {
+    try!(check_source(gl, &source, framebuffer_width, framebuffer_height));
+    let destination = Rect::new(xoffset, yoffset, source.width, source.height);
+    let level_bounds = Rect::from_size(level_width, level_height);
+    if !level_bounds.contains(&destination) {
+        return Err(CopyError::DestinationOutOfBounds(destination, level_bounds))
+    }
+    gl.copy_tex_sub_image_2d(target, level, xoffset, yoffset, source.x, source.y, source.width,
+                             source.height);
+    Ok(())
+}
+
+fn check_source(gl: &Gl, source: &Rect, framebuffer_width: GLsizei, framebuffer_height: GLsizei)
+                -> Result<(), CopyError> {
+    if source.width < 0 || source.height < 0 {
+        return Err(CopyError::InvalidDimensions(source.width, source.height))
+    }
+    let (width, height) = read_framebuffer_size(gl).unwrap_or((framebuffer_width,
+                                                               framebuffer_height));
+    let framebuffer_bounds = Rect::from_size(width, height);
+    if !framebuffer_bounds.contains(source) {
+        return Err(CopyError::SourceOutOfBounds(source.clone(), framebuffer_bounds))
+    }
+    Ok(())
+}
+
+/// Returns the size of the color renderbuffer of the bound framebuffer, or `None` if the default
+/// framebuffer is bound or the color attachment is not a renderbuffer.
+fn read_framebuffer_size(gl: &Gl) -> Option<(GLsizei, GLsizei)> {
+    let mut binding = [0];
+    gl.get_integer_v(gl2::FRAMEBUFFER_BINDING, binding.as_mut_slice());
+    if binding[0] == 0 {
+        return None
+    }
+    match get_attachment(gl, gl2::FRAMEBUFFER, gl2::COLOR_ATTACHMENT0) {
+        Attachment::Renderbuffer { name } => {
+            let (_, width, height) = renderbuffer_storage(gl, name);
+            Some((width, height))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gl2::{RENDERBUFFER, RGBA8, TEXTURE, TEXTURE_2D};
+    use mock;
+    use std::i32;
+    use super::{CopyError, Rect};
+
+    #[test]
+    fn checks_the_default_framebuffer_against_the_callers_size() {
+        mock::reset();
+        let gl = mock::load();
+        let source = Rect::new(0, 0, 100, 10);
+        assert_eq!(super::copy_tex_image_2d(&gl, TEXTURE_2D, 0, RGBA8, source, 200, 200, 0),
+                   Ok(()));
+        let source = Rect::new(150, 0, 100, 10);
+        assert_eq!(super::copy_tex_image_2d(&gl, TEXTURE_2D, 0, RGBA8, source.clone(), 200, 200,
+                                            0),
+                   Err(CopyError::SourceOutOfBounds(source, Rect::from_size(200, 200))));
+    }
+
+    #[test]
+    fn checks_a_renderbuffer_against_its_own_size() {
+        mock::reset();
+        let gl = mock::load();
+        // The framebuffer binding, then its color attachment.
+        mock::push_return("glGetIntegerv", 7);
+        mock::push_return("glGetFramebufferAttachmentParameteriv", RENDERBUFFER as i64);
+        mock::push_return("glGetFramebufferAttachmentParameteriv", 4);
+        // The renderbuffer binding, then the format, width and height of the attachment.
+        mock::push_return("glGetIntegerv", 0);
+        mock::push_return("glGetRenderbufferParameteriv", RGBA8 as i64);
+        mock::push_return("glGetRenderbufferParameteriv", 64);
+        mock::push_return("glGetRenderbufferParameteriv", 32);
+
+        let source = Rect::new(0, 0, 100, 10);
+        assert_eq!(super::copy_tex_image_2d(&gl, TEXTURE_2D, 0, RGBA8, source.clone(), 200, 200,
+                                            0),
+                   Err(CopyError::SourceOutOfBounds(source, Rect::from_size(64, 32))));
+        assert!(mock::calls().iter().all(|call| call.name != "glCopyTexImage2D"));
+    }
+
+    #[test]
+    fn falls_back_to_the_callers_size_for_textures() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetIntegerv", 7);
+        mock::push_return("glGetFramebufferAttachmentParameteriv", TEXTURE as i64);
+        let source = Rect::new(0, 0, 100, 10);
+        assert_eq!(super::copy_tex_sub_image_2d(&gl, TEXTURE_2D, 0, 0, 0, source, 200, 200, 128,
+                                                128),
+                   Ok(()));
+    }
+
+    #[test]
+    fn checks_the_destination_against_the_level_size() {
+        mock::reset();
+        let gl = mock::load();
+        let source = Rect::new(0, 0, 50, 10);
+        assert_eq!(super::copy_tex_sub_image_2d(&gl, TEXTURE_2D, 0, 100, 120, source, 200, 200,
+                                                128, 128),
+                   Err(CopyError::DestinationOutOfBounds(Rect::new(100, 120, 50, 10),
+                                                         Rect::from_size(128, 128))));
+        assert!(mock::calls().iter().all(|call| call.name != "glCopyTexSubImage2D"));
+    }
+
+    #[test]
+    fn contains_rectangles_touching_the_edges() {
+        let bounds = Rect::from_size(100, 50);
+        assert!(bounds.contains(&Rect::new(0, 0, 100, 50)));
+        assert!(bounds.contains(&Rect::new(90, 40, 10, 10)));
+        assert!(bounds.contains(&Rect::new(100, 50, 0, 0)));
+    }
+
+    #[test]
+    fn rejects_rectangles_crossing_the_edges() {
+        let bounds = Rect::from_size(100, 50);
+        assert!(!bounds.contains(&Rect::new(-1, 0, 10, 10)));
+        assert!(!bounds.contains(&Rect::new(0, -1, 10, 10)));
+        assert!(!bounds.contains(&Rect::new(91, 0, 10, 10)));
+        assert!(!bounds.contains(&Rect::new(0, 41, 10, 10)));
+    }
+
+    #[test]
+    fn does_not_overflow_near_the_limits() {
+        let bounds = Rect::from_size(100, 50);
+        assert!(!bounds.contains(&Rect::new(i32::MAX, 0, i32::MAX, 10)));
+        assert!(!bounds.contains(&Rect::new(0, 10, 10, i32::MAX)));
+    }
+}
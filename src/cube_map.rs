@@ -0,0 +1,175 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Uploading the six faces of a cube map texture.
+
+use enums::CubeFace;
+use error::PixelError;
+use gl2;
+use gl2::{Gl, GLenum, GLint, GLsizei};
+use pixels;
+
+/// Every face, in the order of their `TEXTURE_CUBE_MAP_*` targets.
+pub const FACES: [CubeFace, ..6] = [
+    CubeFace::PositiveX,
+    CubeFace::NegativeX,
+    CubeFace::PositiveY,
+    CubeFace::NegativeY,
+    CubeFace::PositiveZ,
+    CubeFace::NegativeZ,
+];
+
+/// Uploads level `level` of all six faces of the cube map bound to `TEXTURE_CUBE_MAP`. The faces
+/// are `size` pixels square and `faces` holds them in the order of `FACES`. With
+/// `generate_mipmap`, the remaining levels are then generated from this one, which OpenGL ES 2.0
+/// only allows for power-of-two sizes.
+///
+/// Nothing is uploaded unless `size` is within `MAX_CUBE_MAP_TEXTURE_SIZE` and every face holds
+/// a whole image.
+pub fn tex_image_cube_map(gl: &Gl,
+                          level: GLint,
+                          internal_format: GLint,
+                          size: GLsizei,
+                          format: GLenum,
+                          ty: GLenum,
+                          faces: &[&[u8], ..6],
+                          generate_mipmap: bool)
+                          -> Result<(), PixelError> {
+    let max = max_cube_map_texture_size(gl);
+    if size > max {
+        return Err(PixelError::TooLarge {
+            size: size,
+            max: max,
+        })
+    }
+    let required = try!(gl.pixel_store().unpack_size(size, size, format, ty));
+    for data in faces.iter() {
+        try!(pixels::check_buffer_size(data.len(), required));
+    }
+
+    for (face, data) in FACES.iter().zip(faces.iter()) {
+        try!(gl.tex_image_2d(face.to_gl(), level, internal_format, size, size, 0, format, ty,
+                             Some(*data)));
+    }
+    if generate_mipmap {
+        gl.generate_mipmap(gl2::TEXTURE_CUBE_MAP);
+    }
+    Ok(())
+}
+
+/// Returns the largest face size the context supports.
+pub fn max_cube_map_texture_size(gl: &Gl) -> GLsizei {
+    let mut result = [0];
+    gl.get_integer_v(gl2::MAX_CUBE_MAP_TEXTURE_SIZE, result.as_mut_slice());
+    result[0]
+}
+
+#[cfg(test)]
+mod test {
+    use error::PixelError;
+    use gl2::{GLint, MAX_CUBE_MAP_TEXTURE_SIZE, RGBA, TEXTURE_CUBE_MAP, UNSIGNED_BYTE};
+    use mock;
+    use super::{FACES, max_cube_map_texture_size, tex_image_cube_map};
+
+    #[test]
+    fn reads_the_maximum_size() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetIntegerv", 2048);
+        assert_eq!(max_cube_map_texture_size(&gl), 2048);
+        let calls = mock::calls();
+        assert_eq!(calls[0].name, "glGetIntegerv");
+        assert_eq!(calls[0].args[0], format!("{}", MAX_CUBE_MAP_TEXTURE_SIZE));
+    }
+
+    #[test]
+    fn uploads_every_face_in_order() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetIntegerv", 16);
+        let face = [0u8, ..4 * 4 * 4];
+        let faces = [face.as_slice(), ..6];
+        assert_eq!(tex_image_cube_map(&gl, 0, RGBA as GLint, 4, RGBA, UNSIGNED_BYTE, &faces, false),
+                   Ok(()));
+        let calls = mock::calls();
+        let uploads: Vec<&mock::Call> = calls.iter()
+                                             .filter(|call| call.name == "glTexImage2D")
+                                             .collect();
+        assert_eq!(uploads.len(), 6);
+        for (call, face) in uploads.iter().zip(FACES.iter()) {
+            assert_eq!(call.args[0], format!("{}", face.to_gl()));
+        }
+        assert!(calls.iter().all(|call| call.name != "glGenerateMipmap"));
+    }
+
+    #[test]
+    fn generates_mipmaps_after_the_last_face() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetIntegerv", 16);
+        let face = [0u8, ..4 * 4 * 4];
+        let faces = [face.as_slice(), ..6];
+        assert_eq!(tex_image_cube_map(&gl, 0, RGBA as GLint, 4, RGBA, UNSIGNED_BYTE, &faces, true),
+                   Ok(()));
+        let calls = mock::calls();
+        let last = calls.last().unwrap();
+        assert_eq!(last.name, "glGenerateMipmap");
+        assert_eq!(last.args[0], format!("{}", TEXTURE_CUBE_MAP));
+    }
+
+    #[test]
+    fn rejects_sizes_above_the_maximum() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetIntegerv", 2);
+        let face = [0u8, ..4 * 4 * 4];
+        let faces = [face.as_slice(), ..6];
+        assert_eq!(tex_image_cube_map(&gl, 0, RGBA as GLint, 4, RGBA, UNSIGNED_BYTE, &faces, false),
+                   Err(PixelError::TooLarge {
+                       size: 4,
+                       max: 2,
+                   }));
+        assert!(mock::calls().iter().all(|call| call.name != "glTexImage2D"));
+    }
+
+    #[test]
+    fn rejects_faces_that_are_not_square() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetIntegerv", 16);
+        // Four pixels wide but only two rows high.
+        let face = [0u8, ..4 * 2 * 4];
+        let faces = [face.as_slice(), ..6];
+        assert_eq!(tex_image_cube_map(&gl, 0, RGBA as GLint, 4, RGBA, UNSIGNED_BYTE, &faces, false),
+                   Err(PixelError::BufferTooSmall {
+                       required: 4 * 4 * 4,
+                       actual: 4 * 2 * 4,
+                   }));
+        assert!(mock::calls().iter().all(|call| call.name != "glTexImage2D"));
+    }
+
+    #[test]
+    fn uploads_nothing_if_any_face_is_short() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetIntegerv", 16);
+        let face = [0u8, ..4 * 4 * 4];
+        let short = [0u8, ..4 * 4 * 4 - 1];
+        let faces = [face.as_slice(), face.as_slice(), face.as_slice(), face.as_slice(),
+                     face.as_slice(), short.as_slice()];
+        assert_eq!(tex_image_cube_map(&gl, 0, RGBA as GLint, 4, RGBA, UNSIGNED_BYTE, &faces, true),
+                   Err(PixelError::BufferTooSmall {
+                       required: 4 * 4 * 4,
+                       actual: 4 * 4 * 4 - 1,
+                   }));
+        assert!(mock::calls().iter().all(|call| {
+            call.name != "glTexImage2D" && call.name != "glGenerateMipmap"
+        }));
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Typed versions of the `GLenum` constants in `gl2`, grouped the way `gl2.h` groups them.
+//!
+//! Every enum converts losslessly to its `GLenum` with `to_gl` and back with `from_gl`, which
+//! returns `None` for values outside the group.
+
+use gl2;
+use gl2::GLenum;
+
+macro_rules! gl_enum {
+    ($(#[$attr:meta])* enum $name:ident { $($variant:ident = $value:ident),+ }) => {
+        $(#[$attr])*
+        #[deriving(Clone, PartialEq, Eq, Hash, Show)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl $name {
+            /// Returns the raw `GLenum` for this value.
+            pub fn to_gl(self) -> GLenum {
+                match self {
+                    $($name::$variant => gl2::$value),+
+                }
+            }
+
+            /// Converts a raw `GLenum`, returning `None` if it is not a member of this group.
+            pub fn from_gl(value: GLenum) -> Option<$name> {
+                $(if value == gl2::$value {
+                    return Some($name::$variant)
+                })+
+                None
+            }
+        }
+    }
+}
+
+gl_enum! {
+    /// Primitive modes for `draw_arrays` and `draw_elements`.
+    enum BeginMode {
+        Points = POINTS,
+        Lines = LINES,
+        LineLoop = LINE_LOOP,
+        LineStrip = LINE_STRIP,
+        Triangles = TRIANGLES,
+        TriangleStrip = TRIANGLE_STRIP,
+        TriangleFan = TRIANGLE_FAN
+    }
+}
+
+gl_enum! {
+    /// Source and destination factors for `blend_func` and `blend_func_separate`.
+    enum BlendingFactor {
+        Zero = ZERO,
+        One = ONE,
+        SrcColor = SRC_COLOR,
+        OneMinusSrcColor = ONE_MINUS_SRC_COLOR,
+        SrcAlpha = SRC_ALPHA,
+        OneMinusSrcAlpha = ONE_MINUS_SRC_ALPHA,
+        DstAlpha = DST_ALPHA,
+        OneMinusDstAlpha = ONE_MINUS_DST_ALPHA,
+        DstColor = DST_COLOR,
+        OneMinusDstColor = ONE_MINUS_DST_COLOR,
+        SrcAlphaSaturate = SRC_ALPHA_SATURATE,
+        ConstantColor = CONSTANT_COLOR,
+        OneMinusConstantColor = ONE_MINUS_CONSTANT_COLOR,
+        ConstantAlpha = CONSTANT_ALPHA,
+        OneMinusConstantAlpha = ONE_MINUS_CONSTANT_ALPHA
+    }
+}
+
+gl_enum! {
+    /// Equations for `blend_equation` and `blend_equation_separate`.
+    enum BlendEquation {
+        FuncAdd = FUNC_ADD,
+        FuncSubtract = FUNC_SUBTRACT,
+        FuncReverseSubtract = FUNC_REVERSE_SUBTRACT
+    }
+}
+
+gl_enum! {
+    /// Component types of vertex attributes, indices and pixels.
+    enum DataType {
+        Byte = BYTE,
+        UnsignedByte = UNSIGNED_BYTE,
+        Short = SHORT,
+        UnsignedShort = UNSIGNED_SHORT,
+        Int = INT,
+        UnsignedInt = UNSIGNED_INT,
+        Float = FLOAT,
+        Fixed = FIXED
+    }
+}
+
+gl_enum! {
+    /// Index types for `draw_elements`. `UnsignedInt` needs the `OES_element_index_uint`
+    /// extension.
+    enum ElementType {
+        UnsignedByte = UNSIGNED_BYTE,
+        UnsignedShort = UNSIGNED_SHORT,
+        UnsignedInt = UNSIGNED_INT
+    }
+}
+
+gl_enum! {
+    /// Component types for `vertex_attrib_pointer`, one for each `AttribComponent` type. Unlike
+    /// `DataType`, this has no `UnsignedInt`, which OpenGL ES 2.0 does not accept there.
+    enum AttribComponentType {
+        Byte = BYTE,
+        UnsignedByte = UNSIGNED_BYTE,
+        Short = SHORT,
+        UnsignedShort = UNSIGNED_SHORT,
+        Int = INT,
+        Fixed = FIXED,
+        Float = FLOAT
+    }
+}
+
+gl_enum! {
+    /// Capabilities for `enable`, `disable` and `is_enabled`.
+    enum Capability {
+        Texture2D = TEXTURE_2D,
+        CullFace = CULL_FACE,
+        Blend = BLEND,
+        Dither = DITHER,
+        StencilTest = STENCIL_TEST,
+        DepthTest = DEPTH_TEST,
+        ScissorTest = SCISSOR_TEST,
+        PolygonOffsetFill = POLYGON_OFFSET_FILL,
+        SampleAlphaToCoverage = SAMPLE_ALPHA_TO_COVERAGE,
+        SampleCoverage = SAMPLE_COVERAGE
+    }
+}
+
+gl_enum! {
+    /// Winding orders for `front_face`.
+    enum FrontFaceDirection {
+        Cw = CW,
+        Ccw = CCW
+    }
+}
+
+gl_enum! {
+    /// Faces for `cull_face`.
+    enum CullFaceMode {
+        Front = FRONT,
+        Back = BACK,
+        FrontAndBack = FRONT_AND_BACK
+    }
+}
+
+gl_enum! {
+    /// Comparison functions for `depth_func` and the stencil test.
+    enum StencilFunction {
+        Never = NEVER,
+        Less = LESS,
+        Equal = EQUAL,
+        Lequal = LEQUAL,
+        Greater = GREATER,
+        Notequal = NOTEQUAL,
+        Gequal = GEQUAL,
+        Always = ALWAYS
+    }
+}
+
+gl_enum! {
+    /// Actions taken on the stencil buffer by `stencil_op`.
+    enum StencilOp {
+        Zero = ZERO,
+        Keep = KEEP,
+        Replace = REPLACE,
+        Incr = INCR,
+        Decr = DECR,
+        Invert = INVERT,
+        IncrWrap = INCR_WRAP,
+        DecrWrap = DECR_WRAP
+    }
+}
+
+gl_enum! {
+    /// Strings returned by `get_string`.
+    enum StringName {
+        Vendor = VENDOR,
+        Renderer = RENDERER,
+        Version = VERSION,
+        ShadingLanguageVersion = SHADING_LANGUAGE_VERSION,
+        Extensions = EXTENSIONS
+    }
+}
+
+gl_enum! {
+    /// Pixel formats for texture uploads and `read_pixels`.
+    enum PixelFormat {
+        DepthComponent = DEPTH_COMPONENT,
+        Alpha = ALPHA,
+        Rgb = RGB,
+        Rgba = RGBA,
+        Luminance = LUMINANCE,
+        LuminanceAlpha = LUMINANCE_ALPHA,
+        Bgra = BGRA
+    }
+}
+
+gl_enum! {
+    /// Pixel component types for texture uploads and `read_pixels`.
+    enum PixelType {
+        UnsignedByte = UNSIGNED_BYTE,
+        UnsignedShort565 = UNSIGNED_SHORT_5_6_5,
+        UnsignedShort4444 = UNSIGNED_SHORT_4_4_4_4,
+        UnsignedShort5551 = UNSIGNED_SHORT_5_5_5_1,
+        Float = FLOAT,
+        UnsignedInt8888Rev = UNSIGNED_INT_8_8_8_8_REV
+    }
+}
+
+gl_enum! {
+    /// Shader types for `create_shader`.
+    enum ShaderType {
+        Fragment = FRAGMENT_SHADER,
+        Vertex = VERTEX_SHADER
+    }
+}
+
+gl_enum! {
+    /// Buffer binding points.
+    enum BufferTarget {
+        Array = ARRAY_BUFFER,
+        ElementArray = ELEMENT_ARRAY_BUFFER
+    }
+}
+
+gl_enum! {
+    /// Usage hints for `buffer_data`.
+    enum BufferUsage {
+        StreamDraw = STREAM_DRAW,
+        StaticDraw = STATIC_DRAW,
+        DynamicDraw = DYNAMIC_DRAW
+    }
+}
+
+gl_enum! {
+    /// Texture binding points.
+    enum TextureTarget {
+        Texture2D = TEXTURE_2D,
+        TextureCubeMap = TEXTURE_CUBE_MAP,
+        TextureRectangleArb = TEXTURE_RECTANGLE_ARB
+    }
+}
+
+gl_enum! {
+    /// The faces of a cube map, which are the targets of its image uploads.
+    enum CubeFace {
+        PositiveX = TEXTURE_CUBE_MAP_POSITIVE_X,
+        NegativeX = TEXTURE_CUBE_MAP_NEGATIVE_X,
+        PositiveY = TEXTURE_CUBE_MAP_POSITIVE_Y,
+        NegativeY = TEXTURE_CUBE_MAP_NEGATIVE_Y,
+        PositiveZ = TEXTURE_CUBE_MAP_POSITIVE_Z,
+        NegativeZ = TEXTURE_CUBE_MAP_NEGATIVE_Z
+    }
+}
+
+gl_enum! {
+    /// Texture parameters for `tex_parameter_i`.
+    enum TextureParameterName {
+        MagFilter = TEXTURE_MAG_FILTER,
+        MinFilter = TEXTURE_MIN_FILTER,
+        WrapS = TEXTURE_WRAP_S,
+        WrapT = TEXTURE_WRAP_T
+    }
+}
+
+gl_enum! {
+    /// Magnification filters.
+    enum TextureMagFilter {
+        Nearest = NEAREST,
+        Linear = LINEAR
+    }
+}
+
+gl_enum! {
+    /// Minification filters.
+    enum TextureMinFilter {
+        Nearest = NEAREST,
+        Linear = LINEAR,
+        NearestMipmapNearest = NEAREST_MIPMAP_NEAREST,
+        LinearMipmapNearest = LINEAR_MIPMAP_NEAREST,
+        NearestMipmapLinear = NEAREST_MIPMAP_LINEAR,
+        LinearMipmapLinear = LINEAR_MIPMAP_LINEAR
+    }
+}
+
+gl_enum! {
+    /// Texture wrap modes.
+    enum TextureWrapMode {
+        Repeat = REPEAT,
+        ClampToEdge = CLAMP_TO_EDGE,
+        MirroredRepeat = MIRRORED_REPEAT
+    }
+}
+
+gl_enum! {
+    /// Targets for `hint`.
+    enum HintTarget {
+        GenerateMipmapHint = GENERATE_MIPMAP_HINT
+    }
+}
+
+gl_enum! {
+    /// Modes for `hint`.
+    enum HintMode {
+        DontCare = DONT_CARE,
+        Fastest = FASTEST,
+        Nicest = NICEST
+    }
+}
+
+gl_enum! {
+    /// Framebuffer binding points.
+    enum FramebufferTarget {
+        Framebuffer = FRAMEBUFFER
+    }
+}
+
+gl_enum! {
+    /// Results of `check_framebuffer_status`.
+    enum FramebufferStatus {
+        Complete = FRAMEBUFFER_COMPLETE,
+        IncompleteAttachment = FRAMEBUFFER_INCOMPLETE_ATTACHMENT,
+        IncompleteMissingAttachment = FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT,
+        IncompleteDimensions = FRAMEBUFFER_INCOMPLETE_DIMENSIONS,
+        Unsupported = FRAMEBUFFER_UNSUPPORTED
+    }
+}
+
+gl_enum! {
+    /// Attachment points of a framebuffer.
+    enum FramebufferAttachment {
+        ColorAttachment0 = COLOR_ATTACHMENT0,
+        DepthAttachment = DEPTH_ATTACHMENT,
+        StencilAttachment = STENCIL_ATTACHMENT,
+        DepthStencilAttachment = DEPTH_STENCIL_ATTACHMENT
+    }
+}
+
+gl_enum! {
+    /// Renderbuffer binding points.
+    enum RenderbufferTarget {
+        Renderbuffer = RENDERBUFFER
+    }
+}
+
+gl_enum! {
+    /// Internal formats for `renderbuffer_storage`.
+    enum RenderbufferInternalFormat {
+        Rgba4 = RGBA4,
+        Rgb5A1 = RGB5_A1,
+        Rgb565 = RGB565,
+        DepthComponent16 = DEPTH_COMPONENT16,
+        StencilIndex8 = STENCIL_INDEX8
+    }
+}
@@ -0,0 +1,338 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Errors reported by `glGetError`, and errors the wrappers detect before calling into GL.
+
+use gl2;
+use gl2::{Gl, GLenum, GLint, GLsizei};
+use std::error::Error;
+use std::fmt;
+
+// Each error flag is reported at most once, but a lost context can make some drivers report
+// errors forever, so draining the queue gives up after this many.
+static MAX_QUEUED_ERRORS: uint = 16;
+
+/// An error code returned by `glGetError`.
+#[deriving(Clone, PartialEq, Eq, Hash)]
+pub enum GlError {
+    InvalidEnum,
+    InvalidValue,
+    InvalidOperation,
+    OutOfMemory,
+    InvalidFramebufferOperation,
+    /// An error code outside of OpenGL ES 2.0, such as `STACK_OVERFLOW` on desktop GL.
+    Unknown(GLenum),
+}
+
+impl GlError {
+    /// Converts a `glGetError` result, returning `None` for `NO_ERROR`.
+    pub fn from_gl(code: GLenum) -> Option<GlError> {
+        match code {
+            gl2::NO_ERROR => None,
+            gl2::INVALID_ENUM => Some(GlError::InvalidEnum),
+            gl2::INVALID_VALUE => Some(GlError::InvalidValue),
+            gl2::INVALID_OPERATION => Some(GlError::InvalidOperation),
+            gl2::OUT_OF_MEMORY => Some(GlError::OutOfMemory),
+            gl2::INVALID_FRAMEBUFFER_OPERATION => Some(GlError::InvalidFramebufferOperation),
+            code => Some(GlError::Unknown(code)),
+        }
+    }
+
+    /// Returns the raw error code.
+    pub fn to_gl(&self) -> GLenum {
+        match *self {
+            GlError::InvalidEnum => gl2::INVALID_ENUM,
+            GlError::InvalidValue => gl2::INVALID_VALUE,
+            GlError::InvalidOperation => gl2::INVALID_OPERATION,
+            GlError::OutOfMemory => gl2::OUT_OF_MEMORY,
+            GlError::InvalidFramebufferOperation => gl2::INVALID_FRAMEBUFFER_OPERATION,
+            GlError::Unknown(code) => code,
+        }
+    }
+}
+
+impl fmt::Show for GlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GlError::Unknown(code) => write!(f, "unknown GL error 0x{:04X}", code),
+            _ => write!(f, "{} (0x{:04X})", self.description(), self.to_gl()),
+        }
+    }
+}
+
+impl Error for GlError {
+    fn description(&self) -> &str {
+        match *self {
+            GlError::InvalidEnum => "GL_INVALID_ENUM",
+            GlError::InvalidValue => "GL_INVALID_VALUE",
+            GlError::InvalidOperation => "GL_INVALID_OPERATION",
+            GlError::OutOfMemory => "GL_OUT_OF_MEMORY",
+            GlError::InvalidFramebufferOperation => "GL_INVALID_FRAMEBUFFER_OPERATION",
+            GlError::Unknown(_) => "unknown GL error",
+        }
+    }
+}
+
+/// A pixel rectangle that a wrapper refused to pass to GL.
+#[deriving(Clone, PartialEq, Eq, Hash)]
+pub enum PixelError {
+    /// The format is not accepted by this call.
+    UnsupportedFormat(GLenum),
+    /// The type is not a pixel type, or cannot be used with the given format.
+    UnsupportedType(GLenum, GLenum),
+    /// The width or height is negative.
+    InvalidDimensions(GLsizei, GLsizei),
+    /// The width or height exceeds the maximum the context supports.
+    TooLarge {
+        size: GLsizei,
+        max: GLsizei,
+    },
+    /// The buffer holds fewer bytes than the rectangle needs.
+    BufferTooSmall {
+        required: uint,
+        actual: uint,
+    },
+    /// The buffer does not hold exactly one compressed image.
+    WrongSize {
+        expected: uint,
+        actual: uint,
+    },
+    /// The width by height rectangle has more bytes than a `uint` can count.
+    Overflow(GLsizei, GLsizei),
+}
+
+impl PixelError {
+    /// Returns the error GL itself would report for the same arguments, or, for a short
+    /// buffer, the one WebGL reports.
+    pub fn to_gl_error(&self) -> GlError {
+        match *self {
+            PixelError::UnsupportedFormat(_) => GlError::InvalidEnum,
+            PixelError::UnsupportedType(..) => GlError::InvalidOperation,
+            PixelError::InvalidDimensions(..) => GlError::InvalidValue,
+            PixelError::TooLarge { .. } => GlError::InvalidValue,
+            PixelError::BufferTooSmall { .. } => GlError::InvalidOperation,
+            PixelError::WrongSize { .. } => GlError::InvalidValue,
+            PixelError::Overflow(..) => GlError::InvalidValue,
+        }
+    }
+}
+
+impl fmt::Show for PixelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PixelError::UnsupportedFormat(format) => {
+                write!(f, "unsupported pixel format 0x{:04X}", format)
+            }
+            PixelError::UnsupportedType(format, ty) => {
+                write!(f, "pixel type 0x{:04X} cannot be used with format 0x{:04X}", ty, format)
+            }
+            PixelError::InvalidDimensions(width, height) => {
+                write!(f, "invalid pixel rectangle size {}x{}", width, height)
+            }
+            PixelError::TooLarge { size, max } => {
+                write!(f, "size {} exceeds the maximum of {}", size, max)
+            }
+            PixelError::BufferTooSmall { required, actual } => {
+                write!(f, "pixel buffer holds {} bytes but {} are needed", actual, required)
+            }
+            PixelError::WrongSize { expected, actual } => {
+                write!(f, "compressed image is {} bytes but should be {}", actual, expected)
+            }
+            PixelError::Overflow(width, height) => {
+                write!(f, "pixel rectangle size {}x{} overflows", width, height)
+            }
+        }
+    }
+}
+
+impl Error for PixelError {
+    fn description(&self) -> &str {
+        match *self {
+            PixelError::UnsupportedFormat(_) => "unsupported pixel format",
+            PixelError::UnsupportedType(..) => "unsupported pixel type for format",
+            PixelError::InvalidDimensions(..) => "negative width or height",
+            PixelError::TooLarge { .. } => "width or height too large",
+            PixelError::BufferTooSmall { .. } => "pixel buffer too small",
+            PixelError::WrongSize { .. } => "compressed image size mismatch",
+            PixelError::Overflow(..) => "pixel rectangle too large to address",
+        }
+    }
+}
+
+/// A parameter array that a wrapper refused to pass to GL because it holds fewer values than
+/// the parameter has, which GL would read past the end of.
+#[deriving(Clone, PartialEq, Eq, Hash)]
+pub struct ParameterError {
+    pub pname: GLenum,
+    /// The number of values the parameter has.
+    pub required: uint,
+    /// The number of values given.
+    pub actual: uint,
+}
+
+impl ParameterError {
+    /// Returns `INVALID_VALUE`. GL itself cannot detect a short array.
+    pub fn to_gl_error(&self) -> GlError {
+        GlError::InvalidValue
+    }
+}
+
+impl fmt::Show for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "parameter 0x{:04X} has {} values but {} were given", self.pname, self.required,
+               self.actual)
+    }
+}
+
+impl Error for ParameterError {
+    fn description(&self) -> &str {
+        "too few parameter values"
+    }
+}
+
+/// Uniform data that a wrapper refused to pass to GL because it is not a whole number of
+/// elements.
+#[deriving(Clone, PartialEq, Eq, Hash)]
+pub struct UniformLengthError {
+    /// The number of values given.
+    pub len: uint,
+    /// The number of values in each element, e.g. 16 for a `mat4`.
+    pub components: uint,
+}
+
+impl UniformLengthError {
+    /// Returns `INVALID_VALUE`. GL itself only sees the whole elements.
+    pub fn to_gl_error(&self) -> GlError {
+        GlError::InvalidValue
+    }
+}
+
+impl fmt::Show for UniformLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "uniform data of length {} is not a multiple of {} components", self.len,
+               self.components)
+    }
+}
+
+impl Error for UniformLengthError {
+    fn description(&self) -> &str {
+        "uniform data not a whole number of elements"
+    }
+}
+
+/// A vertex attribute array that a wrapper refused to pass to GL.
+#[deriving(Clone, PartialEq, Eq, Hash)]
+pub enum VertexAttribError {
+    /// The number of components is not 1 to 4.
+    InvalidSize(GLint),
+    /// The stride is negative.
+    NegativeStride(GLsizei),
+}
+
+impl VertexAttribError {
+    /// Returns `INVALID_VALUE`, which GL itself reports for the same arguments.
+    pub fn to_gl_error(&self) -> GlError {
+        GlError::InvalidValue
+    }
+}
+
+impl fmt::Show for VertexAttribError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VertexAttribError::InvalidSize(size) => {
+                write!(f, "vertex attribute size {} is not between 1 and 4", size)
+            }
+            VertexAttribError::NegativeStride(stride) => {
+                write!(f, "vertex attribute stride {} is negative", stride)
+            }
+        }
+    }
+}
+
+impl Error for VertexAttribError {
+    fn description(&self) -> &str {
+        match *self {
+            VertexAttribError::InvalidSize(_) => "vertex attribute size out of range",
+            VertexAttribError::NegativeStride(_) => "negative vertex attribute stride",
+        }
+    }
+}
+
+/// Indices that a wrapper refused to pass to GL because they cannot be read as asked.
+#[deriving(Clone, PartialEq, Eq, Hash)]
+pub enum IndexCountError {
+    /// The count is negative.
+    Negative(GLsizei),
+    /// The count exceeds the number of indices in the slice.
+    TooMany {
+        count: GLsizei,
+        len: uint,
+    },
+    /// The byte offset into `ELEMENT_ARRAY_BUFFER` is not a multiple of the index size.
+    MisalignedOffset {
+        offset: uint,
+        index_size: uint,
+    },
+}
+
+impl IndexCountError {
+    /// Returns the error GL itself reports for a negative count, or, for indices GL would read
+    /// past the end of the slice or from a misaligned offset, the one WebGL reports.
+    pub fn to_gl_error(&self) -> GlError {
+        match *self {
+            IndexCountError::Negative(_) => GlError::InvalidValue,
+            IndexCountError::TooMany { .. } => GlError::InvalidOperation,
+            IndexCountError::MisalignedOffset { .. } => GlError::InvalidOperation,
+        }
+    }
+}
+
+impl fmt::Show for IndexCountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IndexCountError::Negative(count) => write!(f, "cannot draw {} indices", count),
+            IndexCountError::TooMany { count, len } => {
+                write!(f, "cannot draw {} indices from a slice of {}", count, len)
+            }
+            IndexCountError::MisalignedOffset { offset, index_size } => {
+                write!(f, "index offset {} is not a multiple of the index size {}", offset,
+                       index_size)
+            }
+        }
+    }
+}
+
+impl Error for IndexCountError {
+    fn description(&self) -> &str {
+        match *self {
+            IndexCountError::Negative(_) => "negative index count",
+            IndexCountError::TooMany { .. } => "index count out of range",
+            IndexCountError::MisalignedOffset { .. } => "misaligned index offset",
+        }
+    }
+}
+
+/// Drains the error queue of `gl`, returning the first error it held.
+pub fn drain_errors(gl: &Gl) -> Result<(), GlError> {
+    let mut first = None;
+    for _ in range(0, MAX_QUEUED_ERRORS) {
+        match GlError::from_gl(gl.get_error()) {
+            None => break,
+            Some(error) => {
+                if first.is_none() {
+                    first = Some(error)
+                }
+            }
+        }
+    }
+    match first {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
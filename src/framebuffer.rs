@@ -0,0 +1,474 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Assembling framebuffers from textures and renderbuffers, and explaining why one is incomplete.
+//!
+//! `check_framebuffer_status` only says that something is wrong. When a built framebuffer is
+//! incomplete, `FramebufferBuilder` asks GL what ended up at each attachment point and checks
+//! the size and format of each renderbuffer. OpenGL ES 2.0 cannot query the size of a texture
+//! level, so textures are only checked for being attached at all.
+
+use enums::{CubeFace, FramebufferAttachment, FramebufferStatus, FramebufferTarget};
+use error::GlError;
+use gl2;
+use gl2::{Gl, GLenum, GLint, GLsizei, GLuint};
+use objects::Framebuffer;
+use std::error::Error;
+use std::fmt;
+use typed;
+
+/// An image to attach to a framebuffer.
+#[deriving(Clone, PartialEq, Eq, Hash, Show)]
+pub enum AttachmentSource {
+    /// Level `level` of a texture, where `target` is `TEXTURE_2D` or one of the cube map faces.
+    Texture {
+        name: GLuint,
+        target: GLenum,
+        level: GLint,
+    },
+    Renderbuffer {
+        name: GLuint,
+    },
+}
+
+/// What is attached at one attachment point of a framebuffer, as reported by
+/// `get_framebuffer_attachment_parameter_iv`.
+#[deriving(Clone, PartialEq, Eq, Hash, Show)]
+pub enum Attachment {
+    None,
+    /// Level `level` of a texture, with `face` set if it is a cube map and was read with
+    /// `get_cube_map_attachment`.
+    Texture {
+        name: GLuint,
+        level: GLint,
+        face: Option<CubeFace>,
+    },
+    Renderbuffer {
+        name: GLuint,
+    },
+}
+
+/// Returns what is attached at `attachment` of the framebuffer bound to `target`. The face of a
+/// texture is not queried, so it is always `None`.
+///
+/// OpenGL ES 2.0 reports `INVALID_ENUM` for any other parameter of an empty attachment point, so
+/// only the object type is queried for those.
+pub fn get_attachment(gl: &Gl, target: GLenum, attachment: GLenum) -> Attachment {
+    query_attachment(gl, target, attachment, false)
+}
+
+/// Like `get_attachment`, but also queries the face of a texture. OpenGL ES 2.0 cannot tell
+/// which target a texture was created for, so this is for attachment points that the caller
+/// knows hold a cube map, if anything.
+pub fn get_cube_map_attachment(gl: &Gl, target: GLenum, attachment: GLenum) -> Attachment {
+    query_attachment(gl, target, attachment, true)
+}
+
+fn query_attachment(gl: &Gl, target: GLenum, attachment: GLenum, cube_map: bool) -> Attachment {
+    let get = |pname: GLenum| {
+        gl.get_framebuffer_attachment_parameter_iv(target, attachment, pname)
+    };
+    match get(gl2::FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE) as GLenum {
+        gl2::TEXTURE => {
+            let name = get(gl2::FRAMEBUFFER_ATTACHMENT_OBJECT_NAME) as GLuint;
+            let level = get(gl2::FRAMEBUFFER_ATTACHMENT_TEXTURE_LEVEL);
+            let face = if cube_map {
+                CubeFace::from_gl(get(gl2::FRAMEBUFFER_ATTACHMENT_TEXTURE_CUBE_MAP_FACE) as GLenum)
+            } else {
+                None
+            };
+            Attachment::Texture {
+                name: name,
+                level: level,
+                face: face,
+            }
+        }
+        gl2::RENDERBUFFER => {
+            Attachment::Renderbuffer {
+                name: get(gl2::FRAMEBUFFER_ATTACHMENT_OBJECT_NAME) as GLuint,
+            }
+        }
+        _ => Attachment::None,
+    }
+}
+
+/// Something wrong with one attachment point of an incomplete framebuffer.
+#[deriving(Clone, PartialEq, Eq, Hash)]
+pub enum AttachmentProblem {
+    /// Nothing is attached, usually because the object was deleted or never created.
+    NotAttached(GLenum),
+    /// The renderbuffer has no storage, because `renderbuffer_storage` was never called or
+    /// failed.
+    NoStorage(GLenum),
+    /// The renderbuffer has an internal format, which follows the attachment point, that cannot
+    /// be rendered to there.
+    WrongFormat(GLenum, GLenum),
+    /// The renderbuffer is not the same size as the first renderbuffer attached.
+    WrongSize {
+        attachment: GLenum,
+        width: GLsizei,
+        height: GLsizei,
+        expected_width: GLsizei,
+        expected_height: GLsizei,
+    },
+}
+
+impl fmt::Show for AttachmentProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AttachmentProblem::NotAttached(attachment) => {
+                write!(f, "nothing is attached at {}", AttachmentName(attachment))
+            }
+            AttachmentProblem::NoStorage(attachment) => {
+                write!(f, "the renderbuffer at {} has no storage", AttachmentName(attachment))
+            }
+            AttachmentProblem::WrongFormat(attachment, format) => {
+                write!(f, "the renderbuffer at {} has format 0x{:04X}, which cannot be rendered \
+                           to there", AttachmentName(attachment), format)
+            }
+            AttachmentProblem::WrongSize {
+                attachment,
+                width,
+                height,
+                expected_width,
+                expected_height,
+            } => {
+                write!(f, "the renderbuffer at {} is {}x{} but the first one is {}x{}",
+                       AttachmentName(attachment), width, height, expected_width, expected_height)
+            }
+        }
+    }
+}
+
+struct AttachmentName(GLenum);
+
+impl fmt::Show for AttachmentName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let AttachmentName(attachment) = *self;
+        match FramebufferAttachment::from_gl(attachment) {
+            Some(attachment) => write!(f, "{}", attachment),
+            None => write!(f, "attachment 0x{:04X}", attachment),
+        }
+    }
+}
+
+/// A framebuffer that `FramebufferBuilder` found incomplete.
+#[deriving(Clone, PartialEq, Eq, Hash)]
+pub struct FramebufferError {
+    /// The result of `check_framebuffer_status`, or the raw value if it is not an OpenGL ES 2.0
+    /// status, such as 0 when the check itself failed.
+    pub status: Result<FramebufferStatus, GLenum>,
+    /// What is wrong with each attachment. This is empty when GL rejects the attachments only in
+    /// combination, as with `FRAMEBUFFER_UNSUPPORTED`.
+    pub problems: Vec<AttachmentProblem>,
+}
+
+impl FramebufferError {
+    /// Returns the error GL reports when drawing to or reading from the framebuffer.
+    pub fn to_gl_error(&self) -> GlError {
+        GlError::InvalidFramebufferOperation
+    }
+}
+
+impl fmt::Show for FramebufferError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.status {
+            Ok(status) => try!(write!(f, "framebuffer incomplete: {}", status)),
+            Err(status) => try!(write!(f, "framebuffer incomplete: status 0x{:04X}", status)),
+        }
+        for problem in self.problems.iter() {
+            try!(write!(f, "; {}", problem));
+        }
+        Ok(())
+    }
+}
+
+impl Error for FramebufferError {
+    fn description(&self) -> &str {
+        "incomplete framebuffer"
+    }
+}
+
+/// The attachments of a framebuffer that has yet to be created.
+#[deriving(Clone, Show)]
+pub struct FramebufferBuilder {
+    attachments: Vec<(GLenum, AttachmentSource)>,
+}
+
+impl FramebufferBuilder {
+    pub fn new() -> FramebufferBuilder {
+        FramebufferBuilder {
+            attachments: Vec::new(),
+        }
+    }
+
+    /// Attaches `source` at `attachment`, replacing anything attached there before.
+    pub fn attach(mut self, attachment: GLenum, source: AttachmentSource) -> FramebufferBuilder {
+        self.attachments.retain(|&(other, _)| other != attachment);
+        self.attachments.push((attachment, source));
+        self
+    }
+
+    /// Attaches level `level` of `texture` as the color buffer. `target` is `TEXTURE_2D` or, for
+    /// a cube map, the face to render to.
+    pub fn color_texture(self, target: GLenum, texture: GLuint, level: GLint)
+                         -> FramebufferBuilder {
+        self.attach(gl2::COLOR_ATTACHMENT0, AttachmentSource::Texture {
+            name: texture,
+            target: target,
+            level: level,
+        })
+    }
+
+    /// Attaches `renderbuffer` as the color buffer.
+    pub fn color_renderbuffer(self, renderbuffer: GLuint) -> FramebufferBuilder {
+        self.attach(gl2::COLOR_ATTACHMENT0, AttachmentSource::Renderbuffer { name: renderbuffer })
+    }
+
+    /// Attaches `renderbuffer` as the depth buffer.
+    pub fn depth_renderbuffer(self, renderbuffer: GLuint) -> FramebufferBuilder {
+        self.attach(gl2::DEPTH_ATTACHMENT, AttachmentSource::Renderbuffer { name: renderbuffer })
+    }
+
+    /// Attaches `renderbuffer` as the stencil buffer.
+    pub fn stencil_renderbuffer(self, renderbuffer: GLuint) -> FramebufferBuilder {
+        self.attach(gl2::STENCIL_ATTACHMENT, AttachmentSource::Renderbuffer { name: renderbuffer })
+    }
+
+    /// Attaches `renderbuffer` as both the depth and stencil buffer. Only WebGL and desktop GL
+    /// have a combined attachment point.
+    pub fn depth_stencil_renderbuffer(self, renderbuffer: GLuint) -> FramebufferBuilder {
+        self.attach(gl2::DEPTH_STENCIL_ATTACHMENT,
+                    AttachmentSource::Renderbuffer { name: renderbuffer })
+    }
+
+    /// Creates a framebuffer with these attachments and leaves it bound to `FRAMEBUFFER`.
+    ///
+    /// If the framebuffer is incomplete it is deleted, which binds the default framebuffer, and
+    /// the error lists what is wrong with each attachment.
+    pub fn build<'a>(&self, gl: &'a Gl) -> Result<Framebuffer<'a>, FramebufferError> {
+        let framebuffer = Framebuffer::new(gl);
+        gl.bind_framebuffer(gl2::FRAMEBUFFER, framebuffer.name());
+        for &(attachment, ref source) in self.attachments.iter() {
+            match *source {
+                AttachmentSource::Texture { name, target, level } => {
+                    gl.framebuffer_texture_2d(gl2::FRAMEBUFFER, attachment, target, name, level)
+                }
+                AttachmentSource::Renderbuffer { name } => {
+                    gl.framebuffer_renderbuffer(gl2::FRAMEBUFFER, attachment, gl2::RENDERBUFFER,
+                                                name)
+                }
+            }
+        }
+
+        let status = typed::check_framebuffer_status(gl, FramebufferTarget::Framebuffer);
+        if status != Ok(FramebufferStatus::Complete) {
+            return Err(FramebufferError {
+                status: status,
+                problems: self.diagnose(gl),
+            })
+        }
+        Ok(framebuffer)
+    }
+
+    /// Checks each attachment of the framebuffer bound to `FRAMEBUFFER`.
+    fn diagnose(&self, gl: &Gl) -> Vec<AttachmentProblem> {
+        let mut problems = Vec::new();
+        let mut expected_size = None;
+        for &(attachment, ref source) in self.attachments.iter() {
+            if get_attachment(gl, gl2::FRAMEBUFFER, attachment) == Attachment::None {
+                problems.push(AttachmentProblem::NotAttached(attachment));
+                continue
+            }
+
+            let renderbuffer = match *source {
+                AttachmentSource::Renderbuffer { name } => name,
+                AttachmentSource::Texture { .. } => continue,
+            };
+            let (format, width, height) = renderbuffer_storage(gl, renderbuffer);
+            if width == 0 || height == 0 {
+                problems.push(AttachmentProblem::NoStorage(attachment));
+                continue
+            }
+            if !is_renderable(attachment, format) {
+                problems.push(AttachmentProblem::WrongFormat(attachment, format));
+            }
+            match expected_size {
+                None => expected_size = Some((width, height)),
+                Some((expected_width, expected_height)) => {
+                    if (width, height) != (expected_width, expected_height) {
+                        problems.push(AttachmentProblem::WrongSize {
+                            attachment: attachment,
+                            width: width,
+                            height: height,
+                            expected_width: expected_width,
+                            expected_height: expected_height,
+                        });
+                    }
+                }
+            }
+        }
+        problems
+    }
+}
+
+/// Returns the internal format, width and height of `renderbuffer`, leaving the renderbuffer
+/// binding as it was.
+pub fn renderbuffer_storage(gl: &Gl, renderbuffer: GLuint) -> (GLenum, GLsizei, GLsizei) {
+    let mut binding = [0];
+    gl.get_integer_v(gl2::RENDERBUFFER_BINDING, binding.as_mut_slice());
+    gl.bind_renderbuffer(gl2::RENDERBUFFER, renderbuffer);
+    let format = gl.get_renderbuffer_parameter_iv(gl2::RENDERBUFFER,
+                                                  gl2::RENDERBUFFER_INTERNAL_FORMAT);
+    let width = gl.get_renderbuffer_parameter_iv(gl2::RENDERBUFFER, gl2::RENDERBUFFER_WIDTH);
+    let height = gl.get_renderbuffer_parameter_iv(gl2::RENDERBUFFER, gl2::RENDERBUFFER_HEIGHT);
+    gl.bind_renderbuffer(gl2::RENDERBUFFER, binding[0] as GLuint);
+    (format as GLenum, width, height)
+}
+
+/// Whether a renderbuffer of `format` can be attached at `attachment`. Formats from extensions
+/// are given the benefit of the doubt.
+fn is_renderable(attachment: GLenum, format: GLenum) -> bool {
+    let attachment = match attachment {
+        gl2::DEPTH_ATTACHMENT | gl2::STENCIL_ATTACHMENT | gl2::DEPTH_STENCIL_ATTACHMENT => {
+            attachment
+        }
+        _ => gl2::COLOR_ATTACHMENT0,
+    };
+    match format {
+        gl2::RGBA4 | gl2::RGB5_A1 | gl2::RGB565 | gl2::RGBA8 => {
+            attachment == gl2::COLOR_ATTACHMENT0
+        }
+        gl2::DEPTH_COMPONENT16 => attachment == gl2::DEPTH_ATTACHMENT,
+        gl2::STENCIL_INDEX8 => attachment == gl2::STENCIL_ATTACHMENT,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use enums::{CubeFace, FramebufferStatus};
+    use gl2::{COLOR_ATTACHMENT0, DEPTH_ATTACHMENT, FRAMEBUFFER, FRAMEBUFFER_INCOMPLETE_ATTACHMENT};
+    use gl2::{FRAMEBUFFER_ATTACHMENT_OBJECT_NAME, FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE};
+    use gl2::{FRAMEBUFFER_ATTACHMENT_TEXTURE_CUBE_MAP_FACE, FRAMEBUFFER_ATTACHMENT_TEXTURE_LEVEL};
+    use gl2::{GLenum, NONE, RENDERBUFFER, RGBA4, TEXTURE, TEXTURE_CUBE_MAP_POSITIVE_Y};
+    use mock;
+    use super::{Attachment, AttachmentProblem, FramebufferBuilder, get_attachment};
+    use super::get_cube_map_attachment;
+
+    // The parameters queried with `glGetFramebufferAttachmentParameteriv`, in order.
+    fn queried_parameters() -> Vec<String> {
+        mock::calls().iter()
+                     .filter(|call| call.name == "glGetFramebufferAttachmentParameteriv")
+                     .map(|call| call.args[2].clone())
+                     .collect()
+    }
+
+    fn names(pnames: &[GLenum]) -> Vec<String> {
+        pnames.iter().map(|pname| format!("{}", pname)).collect()
+    }
+
+    #[test]
+    fn reads_a_texture_attachment() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetFramebufferAttachmentParameteriv", TEXTURE as i64);
+        mock::push_return("glGetFramebufferAttachmentParameteriv", 4);
+        mock::push_return("glGetFramebufferAttachmentParameteriv", 2);
+        assert_eq!(get_attachment(&gl, FRAMEBUFFER, COLOR_ATTACHMENT0), Attachment::Texture {
+            name: 4,
+            level: 2,
+            face: None,
+        });
+        assert_eq!(queried_parameters(), names(&[FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE,
+                                                 FRAMEBUFFER_ATTACHMENT_OBJECT_NAME,
+                                                 FRAMEBUFFER_ATTACHMENT_TEXTURE_LEVEL]));
+    }
+
+    #[test]
+    fn reads_the_face_of_a_cube_map_attachment() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetFramebufferAttachmentParameteriv", TEXTURE as i64);
+        mock::push_return("glGetFramebufferAttachmentParameteriv", 4);
+        mock::push_return("glGetFramebufferAttachmentParameteriv", 0);
+        mock::push_return("glGetFramebufferAttachmentParameteriv",
+                          TEXTURE_CUBE_MAP_POSITIVE_Y as i64);
+        assert_eq!(get_cube_map_attachment(&gl, FRAMEBUFFER, COLOR_ATTACHMENT0),
+                   Attachment::Texture {
+                       name: 4,
+                       level: 0,
+                       face: Some(CubeFace::PositiveY),
+                   });
+        assert_eq!(queried_parameters()[3],
+                   format!("{}", FRAMEBUFFER_ATTACHMENT_TEXTURE_CUBE_MAP_FACE));
+    }
+
+    #[test]
+    fn reads_a_renderbuffer_attachment() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetFramebufferAttachmentParameteriv", RENDERBUFFER as i64);
+        mock::push_return("glGetFramebufferAttachmentParameteriv", 6);
+        assert_eq!(get_cube_map_attachment(&gl, FRAMEBUFFER, DEPTH_ATTACHMENT),
+                   Attachment::Renderbuffer { name: 6 });
+        assert_eq!(queried_parameters(), names(&[FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE,
+                                                 FRAMEBUFFER_ATTACHMENT_OBJECT_NAME]));
+    }
+
+    #[test]
+    fn reads_only_the_type_of_an_empty_attachment() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetFramebufferAttachmentParameteriv", NONE as i64);
+        assert_eq!(get_cube_map_attachment(&gl, FRAMEBUFFER, COLOR_ATTACHMENT0), Attachment::None);
+        assert_eq!(queried_parameters(), names(&[FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE]));
+    }
+
+    #[test]
+    fn complete_framebuffer_builds() {
+        mock::reset();
+        let gl = mock::load();
+        assert!(FramebufferBuilder::new().color_renderbuffer(1).build(&gl).is_ok());
+    }
+
+    #[test]
+    fn reports_status_and_problems() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glCheckFramebufferStatus", FRAMEBUFFER_INCOMPLETE_ATTACHMENT as i64);
+        // The object type and name of the color attachment.
+        mock::push_return("glGetFramebufferAttachmentParameteriv", RENDERBUFFER as i64);
+        mock::push_return("glGetFramebufferAttachmentParameteriv", 5);
+        // Its internal format, width and height.
+        mock::push_return("glGetRenderbufferParameteriv", RGBA4 as i64);
+        mock::push_return("glGetRenderbufferParameteriv", 0);
+        mock::push_return("glGetRenderbufferParameteriv", 0);
+
+        let error = FramebufferBuilder::new().color_renderbuffer(5).build(&gl).err().unwrap();
+        assert_eq!(error.status, Ok(FramebufferStatus::IncompleteAttachment));
+        assert_eq!(error.problems, vec![AttachmentProblem::NoStorage(COLOR_ATTACHMENT0)]);
+    }
+
+    #[test]
+    fn keeps_unknown_status() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glCheckFramebufferStatus", 0);
+        mock::push_return("glGetFramebufferAttachmentParameteriv", RENDERBUFFER as i64);
+        mock::push_return("glGetFramebufferAttachmentParameteriv", 3);
+        mock::push_return("glGetRenderbufferParameteriv", RGBA4 as i64);
+        mock::push_return("glGetRenderbufferParameteriv", 16);
+        mock::push_return("glGetRenderbufferParameteriv", 16);
+
+        let error = FramebufferBuilder::new().depth_renderbuffer(3).build(&gl).err().unwrap();
+        assert_eq!(error.status, Err(0));
+        assert_eq!(error.problems, vec![AttachmentProblem::WrongFormat(DEPTH_ATTACHMENT, RGBA4)]);
+    }
+}
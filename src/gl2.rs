@@ -9,9 +9,17 @@
 
 /* automatically generated by rust-bindgen */
 
+pub use self::astc::*;
+
+use compressed;
+use error::{IndexCountError, ParameterError, PixelError, UniformLengthError};
+use error::VertexAttribError;
+use pixels;
+use pixels::{PixelStore, INITIAL_PIXEL_STORE};
 use libc::{c_uint, c_uchar, c_void, c_char, int8_t, c_short, c_int, uint8_t, c_ushort};
 use libc::{int32_t, intptr_t, ssize_t};
 use std::mem;
+use std::cell::UnsafeCell;
 use std::cmp;
 use std::ptr;
 use std::str::from_utf8;
@@ -20,15 +28,15 @@ use std::mem::size_of;
 use std::vec::Vec;
 
 // Linking
-#[cfg(target_os = "macos")]
+#[cfg(all(feature = "static_link", target_os = "macos"))]
 #[link(name = "OpenGL", kind = "framework")]
 extern { }
 
-#[cfg(target_os = "linux")]
+#[cfg(all(feature = "static_link", target_os = "linux"))]
 #[link(name = "GL")]
 extern { }
 
-#[cfg(target_os = "android")]
+#[cfg(all(feature = "static_link", target_os = "android"))]
 #[link(name = "GLESv2")]
 extern { }
 
@@ -179,6 +187,8 @@ pub const SAMPLE_BUFFERS:                c_uint = 0x80A8 as c_uint;
 pub const SAMPLES:                       c_uint = 0x80A9 as c_uint;
 pub const SAMPLE_COVERAGE_VALUE:         c_uint = 0x80AA as c_uint;
 pub const SAMPLE_COVERAGE_INVERT:        c_uint = 0x80AB as c_uint;
+pub const NUM_COMPRESSED_TEXTURE_FORMATS: c_uint = 0x86A2 as c_uint;
+pub const COMPRESSED_TEXTURE_FORMATS:    c_uint = 0x86A3 as c_uint;
 
 /* GetTarget */
 pub const UNPACK_ROW_LENGTH: c_uint = 0x0CF2 as c_uint;
@@ -191,11 +201,16 @@ pub const BLUE:            c_uint = 0x1905 as c_uint;
 pub const ALPHA:           c_uint = 0x1906 as c_uint;
 pub const RGB:             c_uint = 0x1907 as c_uint;
 pub const RGBA:            c_uint = 0x1908 as c_uint;
+pub const LUMINANCE:       c_uint = 0x1909 as c_uint;
+pub const LUMINANCE_ALPHA: c_uint = 0x190A as c_uint;
 
 pub const BGRA:            c_uint = 0x80e1 as c_uint;   // NB: Not OpenGL ES!
 pub const RGBA8:           c_uint = 0x8058 as c_uint;   // NB: Not OpenGL ES!
 
 /* Packed Pixels */
+pub const UNSIGNED_SHORT_4_4_4_4:   c_uint = 0x8033 as c_uint;
+pub const UNSIGNED_SHORT_5_5_5_1:   c_uint = 0x8034 as c_uint;
+pub const UNSIGNED_SHORT_5_6_5:     c_uint = 0x8363 as c_uint;
 pub const UNSIGNED_INT_8_8_8_8_REV: c_uint = 0x8367 as c_uint; // NB: Not OpenGL ES!
 
 /* Shaders */
@@ -220,6 +235,23 @@ pub const ACTIVE_ATTRIBUTE_MAX_LENGTH:      c_uint = 0x8B8A as c_uint;
 pub const SHADING_LANGUAGE_VERSION:         c_uint = 0x8B8C as c_uint;
 pub const CURRENT_PROGRAM:                  c_uint = 0x8B8D as c_uint;
 
+/* Uniform Types */
+pub const FLOAT_VEC2:   c_uint = 0x8B50 as c_uint;
+pub const FLOAT_VEC3:   c_uint = 0x8B51 as c_uint;
+pub const FLOAT_VEC4:   c_uint = 0x8B52 as c_uint;
+pub const INT_VEC2:     c_uint = 0x8B53 as c_uint;
+pub const INT_VEC3:     c_uint = 0x8B54 as c_uint;
+pub const INT_VEC4:     c_uint = 0x8B55 as c_uint;
+pub const BOOL:         c_uint = 0x8B56 as c_uint;
+pub const BOOL_VEC2:    c_uint = 0x8B57 as c_uint;
+pub const BOOL_VEC3:    c_uint = 0x8B58 as c_uint;
+pub const BOOL_VEC4:    c_uint = 0x8B59 as c_uint;
+pub const FLOAT_MAT2:   c_uint = 0x8B5A as c_uint;
+pub const FLOAT_MAT3:   c_uint = 0x8B5B as c_uint;
+pub const FLOAT_MAT4:   c_uint = 0x8B5C as c_uint;
+pub const SAMPLER_2D:   c_uint = 0x8B5E as c_uint;
+pub const SAMPLER_CUBE: c_uint = 0x8B60 as c_uint;
+
 /* StencilFunction */
 pub const NEVER:    c_uint = 0x0200 as c_uint;
 pub const LESS:     c_uint = 0x0201 as c_uint;
@@ -230,6 +262,16 @@ pub const NOTEQUAL: c_uint = 0x0205 as c_uint;
 pub const GEQUAL:   c_uint = 0x0206 as c_uint;
 pub const ALWAYS:   c_uint = 0x0207 as c_uint;
 
+/* StencilOp */
+/*      ZERO */
+pub const KEEP:      c_uint = 0x1E00 as c_uint;
+pub const REPLACE:   c_uint = 0x1E01 as c_uint;
+pub const INCR:      c_uint = 0x1E02 as c_uint;
+pub const DECR:      c_uint = 0x1E03 as c_uint;
+pub const INVERT:    c_uint = 0x150A as c_uint;
+pub const INCR_WRAP: c_uint = 0x8507 as c_uint;
+pub const DECR_WRAP: c_uint = 0x8508 as c_uint;
+
 pub const VENDOR:     c_uint = 0x1F00 as c_uint;
 pub const RENDERER:   c_uint = 0x1F01 as c_uint;
 pub const VERSION:    c_uint = 0x1F02 as c_uint;
@@ -251,6 +293,16 @@ pub const STREAM_DRAW:  c_uint = 0x88E0 as c_uint;
 pub const STATIC_DRAW:  c_uint = 0x88E4 as c_uint;
 pub const DYNAMIC_DRAW: c_uint = 0x88E8 as c_uint;
 
+/* Vertex Arrays */
+pub const VERTEX_ATTRIB_ARRAY_ENABLED:        c_uint = 0x8622 as c_uint;
+pub const VERTEX_ATTRIB_ARRAY_SIZE:           c_uint = 0x8623 as c_uint;
+pub const VERTEX_ATTRIB_ARRAY_STRIDE:         c_uint = 0x8624 as c_uint;
+pub const VERTEX_ATTRIB_ARRAY_TYPE:           c_uint = 0x8625 as c_uint;
+pub const CURRENT_VERTEX_ATTRIB:              c_uint = 0x8626 as c_uint;
+pub const VERTEX_ATTRIB_ARRAY_NORMALIZED:     c_uint = 0x886A as c_uint;
+pub const VERTEX_ATTRIB_ARRAY_POINTER:        c_uint = 0x8645 as c_uint;
+pub const VERTEX_ATTRIB_ARRAY_BUFFER_BINDING: c_uint = 0x889F as c_uint;
+
 /* CullFaceMode */
 pub const FRONT: c_uint =           0x0404 as c_uint;
 pub const BACK: c_uint =            0x0405 as c_uint;
@@ -260,12 +312,41 @@ pub const FRONT_AND_BACK: c_uint =  0x0408 as c_uint;
 pub const NEAREST: c_uint = 0x2600 as c_uint;
 pub const LINEAR:  c_uint = 0x2601 as c_uint;
 
+/* TextureMinFilter */
+/*      NEAREST */
+/*      LINEAR */
+pub const NEAREST_MIPMAP_NEAREST: c_uint = 0x2700 as c_uint;
+pub const LINEAR_MIPMAP_NEAREST:  c_uint = 0x2701 as c_uint;
+pub const NEAREST_MIPMAP_LINEAR:  c_uint = 0x2702 as c_uint;
+pub const LINEAR_MIPMAP_LINEAR:   c_uint = 0x2703 as c_uint;
+
 /* TextureParameterName */
 pub const TEXTURE_MAG_FILTER: c_uint = 0x2800 as c_uint;
 pub const TEXTURE_MIN_FILTER: c_uint = 0x2801 as c_uint;
 pub const TEXTURE_WRAP_S:     c_uint = 0x2802 as c_uint;
 pub const TEXTURE_WRAP_T:     c_uint = 0x2803 as c_uint;
 
+/* TextureTarget */
+/*      TEXTURE_2D */
+pub const TEXTURE:                     c_uint = 0x1702 as c_uint;
+pub const TEXTURE_CUBE_MAP:            c_uint = 0x8513 as c_uint;
+pub const TEXTURE_BINDING_CUBE_MAP:    c_uint = 0x8514 as c_uint;
+pub const TEXTURE_CUBE_MAP_POSITIVE_X: c_uint = 0x8515 as c_uint;
+pub const TEXTURE_CUBE_MAP_NEGATIVE_X: c_uint = 0x8516 as c_uint;
+pub const TEXTURE_CUBE_MAP_POSITIVE_Y: c_uint = 0x8517 as c_uint;
+pub const TEXTURE_CUBE_MAP_NEGATIVE_Y: c_uint = 0x8518 as c_uint;
+pub const TEXTURE_CUBE_MAP_POSITIVE_Z: c_uint = 0x8519 as c_uint;
+pub const TEXTURE_CUBE_MAP_NEGATIVE_Z: c_uint = 0x851A as c_uint;
+pub const MAX_CUBE_MAP_TEXTURE_SIZE:   c_uint = 0x851C as c_uint;
+
+/* HintMode */
+pub const DONT_CARE: c_uint = 0x1100 as c_uint;
+pub const FASTEST:   c_uint = 0x1101 as c_uint;
+pub const NICEST:    c_uint = 0x1102 as c_uint;
+
+/* HintTarget */
+pub const GENERATE_MIPMAP_HINT: c_uint = 0x8192 as c_uint;
+
 /* TextureUnit */
 pub const TEXTURE0:       c_uint = 0x84C0 as c_uint;
 pub const TEXTURE1:       c_uint = 0x84C1 as c_uint;
@@ -308,12 +389,47 @@ pub const MIRRORED_REPEAT: c_uint = 0x8370 as c_uint;
 
 pub const COLOR_ATTACHMENT0: c_uint = 0x8CE0 as c_uint;
 
-pub const FRAMEBUFFER_COMPLETE: c_uint = 0x8CD5 as c_uint;
+pub const FRAMEBUFFER_COMPLETE:                      c_uint = 0x8CD5 as c_uint;
+pub const FRAMEBUFFER_INCOMPLETE_ATTACHMENT:         c_uint = 0x8CD6 as c_uint;
+pub const FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT: c_uint = 0x8CD7 as c_uint;
+pub const FRAMEBUFFER_INCOMPLETE_DIMENSIONS:         c_uint = 0x8CD9 as c_uint;
+pub const FRAMEBUFFER_UNSUPPORTED:                   c_uint = 0x8CDD as c_uint;
 
 // Framebuffer Object
 pub const FRAMEBUFFER:  c_uint = 0x8D40 as c_uint;
 pub const RENDERBUFFER: c_uint = 0x8D41 as c_uint;
 
+pub const RGBA4:             c_uint = 0x8056 as c_uint;
+pub const RGB5_A1:           c_uint = 0x8057 as c_uint;
+pub const RGB565:            c_uint = 0x8D62 as c_uint;
+pub const DEPTH_COMPONENT16: c_uint = 0x81A5 as c_uint;
+pub const STENCIL_INDEX8:    c_uint = 0x8D48 as c_uint;
+
+pub const RENDERBUFFER_WIDTH:           c_uint = 0x8D42 as c_uint;
+pub const RENDERBUFFER_HEIGHT:          c_uint = 0x8D43 as c_uint;
+pub const RENDERBUFFER_INTERNAL_FORMAT: c_uint = 0x8D44 as c_uint;
+pub const RENDERBUFFER_RED_SIZE:        c_uint = 0x8D50 as c_uint;
+pub const RENDERBUFFER_GREEN_SIZE:      c_uint = 0x8D51 as c_uint;
+pub const RENDERBUFFER_BLUE_SIZE:       c_uint = 0x8D52 as c_uint;
+pub const RENDERBUFFER_ALPHA_SIZE:      c_uint = 0x8D53 as c_uint;
+pub const RENDERBUFFER_DEPTH_SIZE:      c_uint = 0x8D54 as c_uint;
+pub const RENDERBUFFER_STENCIL_SIZE:    c_uint = 0x8D55 as c_uint;
+
+pub const FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE:           c_uint = 0x8CD0 as c_uint;
+pub const FRAMEBUFFER_ATTACHMENT_OBJECT_NAME:           c_uint = 0x8CD1 as c_uint;
+pub const FRAMEBUFFER_ATTACHMENT_TEXTURE_LEVEL:         c_uint = 0x8CD2 as c_uint;
+pub const FRAMEBUFFER_ATTACHMENT_TEXTURE_CUBE_MAP_FACE: c_uint = 0x8CD3 as c_uint;
+
+pub const DEPTH_ATTACHMENT:         c_uint = 0x8D00 as c_uint;
+pub const STENCIL_ATTACHMENT:       c_uint = 0x8D20 as c_uint;
+pub const DEPTH_STENCIL_ATTACHMENT: c_uint = 0x821A as c_uint;    // NB: Not OpenGL ES!
+
+pub const NONE: c_uint = 0 as c_uint;
+
+pub const FRAMEBUFFER_BINDING:   c_uint = 0x8CA6 as c_uint;
+pub const RENDERBUFFER_BINDING:  c_uint = 0x8CA7 as c_uint;
+pub const MAX_RENDERBUFFER_SIZE: c_uint = 0x84E8 as c_uint;
+
 // Extensions
 pub const TEXTURE_RECTANGLE_ARB: c_uint = 0x84F5 as c_uint;         // NB: Not OpenGL ES!
 
@@ -322,6 +438,65 @@ pub const TEXTURE_STORAGE_HINT_APPLE: c_uint = 0x85BC as c_uint;    // NB: Not O
 pub const STORAGE_CACHED_APPLE: c_uint = 0x85BE as c_uint;          // NB: Not OpenGL ES!
 pub const STORAGE_SHARED_APPLE: c_uint = 0x85BF as c_uint;          // NB: Not OpenGL ES!
 
+/* Compressed texture formats */
+pub const ETC1_RGB8_OES: c_uint = 0x8D64 as c_uint;
+
+pub const COMPRESSED_R11_EAC:                        c_uint = 0x9270 as c_uint;
+pub const COMPRESSED_SIGNED_R11_EAC:                 c_uint = 0x9271 as c_uint;
+pub const COMPRESSED_RG11_EAC:                       c_uint = 0x9272 as c_uint;
+pub const COMPRESSED_SIGNED_RG11_EAC:                c_uint = 0x9273 as c_uint;
+pub const COMPRESSED_RGB8_ETC2:                      c_uint = 0x9274 as c_uint;
+pub const COMPRESSED_SRGB8_ETC2:                     c_uint = 0x9275 as c_uint;
+pub const COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2:  c_uint = 0x9276 as c_uint;
+pub const COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2: c_uint = 0x9277 as c_uint;
+pub const COMPRESSED_RGBA8_ETC2_EAC:                 c_uint = 0x9278 as c_uint;
+pub const COMPRESSED_SRGB8_ALPHA8_ETC2_EAC:          c_uint = 0x9279 as c_uint;
+
+pub const COMPRESSED_RGB_S3TC_DXT1_EXT:  c_uint = 0x83F0 as c_uint;
+pub const COMPRESSED_RGBA_S3TC_DXT1_EXT: c_uint = 0x83F1 as c_uint;
+pub const COMPRESSED_RGBA_S3TC_DXT3_EXT: c_uint = 0x83F2 as c_uint;
+pub const COMPRESSED_RGBA_S3TC_DXT5_EXT: c_uint = 0x83F3 as c_uint;
+
+pub const COMPRESSED_RGB_PVRTC_4BPPV1_IMG:  c_uint = 0x8C00 as c_uint;
+pub const COMPRESSED_RGB_PVRTC_2BPPV1_IMG:  c_uint = 0x8C01 as c_uint;
+pub const COMPRESSED_RGBA_PVRTC_4BPPV1_IMG: c_uint = 0x8C02 as c_uint;
+pub const COMPRESSED_RGBA_PVRTC_2BPPV1_IMG: c_uint = 0x8C03 as c_uint;
+
+// The ASTC format names keep the lowercase `x` of their block sizes.
+#[allow(non_upper_case_globals)]
+mod astc {
+    use libc::c_uint;
+
+    pub const COMPRESSED_RGBA_ASTC_4x4_KHR:           c_uint = 0x93B0 as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_5x4_KHR:           c_uint = 0x93B1 as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_5x5_KHR:           c_uint = 0x93B2 as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_6x5_KHR:           c_uint = 0x93B3 as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_6x6_KHR:           c_uint = 0x93B4 as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_8x5_KHR:           c_uint = 0x93B5 as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_8x6_KHR:           c_uint = 0x93B6 as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_8x8_KHR:           c_uint = 0x93B7 as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_10x5_KHR:          c_uint = 0x93B8 as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_10x6_KHR:          c_uint = 0x93B9 as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_10x8_KHR:          c_uint = 0x93BA as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_10x10_KHR:         c_uint = 0x93BB as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_12x10_KHR:         c_uint = 0x93BC as c_uint;
+    pub const COMPRESSED_RGBA_ASTC_12x12_KHR:         c_uint = 0x93BD as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_4x4_KHR:   c_uint = 0x93D0 as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_5x4_KHR:   c_uint = 0x93D1 as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_5x5_KHR:   c_uint = 0x93D2 as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_6x5_KHR:   c_uint = 0x93D3 as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_6x6_KHR:   c_uint = 0x93D4 as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_8x5_KHR:   c_uint = 0x93D5 as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_8x6_KHR:   c_uint = 0x93D6 as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_8x8_KHR:   c_uint = 0x93D7 as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_10x5_KHR:  c_uint = 0x93D8 as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_10x6_KHR:  c_uint = 0x93D9 as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_10x8_KHR:  c_uint = 0x93DA as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_10x10_KHR: c_uint = 0x93DB as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_12x10_KHR: c_uint = 0x93DC as c_uint;
+    pub const COMPRESSED_SRGB8_ALPHA8_ASTC_12x12_KHR: c_uint = 0x93DD as c_uint;
+}
+
 
 // Types
 
@@ -364,527 +539,2256 @@ pub type GLeglImageOES = *mut c_void;
 
 // Exposed Rust API using Rust naming conventions
 
-pub fn active_texture(texture: GLenum) {
-    unsafe {
-        glActiveTexture(texture);
+/// An active uniform or attribute of a linked program.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct ActiveInfo {
+    /// The name, with a `[0]` suffix for arrays.
+    pub name: String,
+    /// The number of array elements, or 1.
+    pub size: GLint,
+    /// The type, e.g. `FLOAT_VEC4` or `SAMPLER_2D`.
+    pub ty: GLenum,
+}
+
+/// The entry points of one GL context. Each context in a process can carry its own `Gl`,
+/// loaded with whatever function pointers that context provides.
+pub struct Gl {
+    fns: Dispatch,
+    pixel_store: UnsafeCell<PixelStore>,
+    // Whether the context has `UNPACK_ROW_LENGTH`, found on first use.
+    has_unpack_row_length: UnsafeCell<Option<bool>>,
+}
+
+// The entry points of a `Gl`, and how many `DebugChecksSuspended` guards are alive for it.
+struct Dispatch {
+    raw: FnPtrs,
+    suspended: UnsafeCell<uint>,
+}
+
+impl Dispatch {
+    fn new(raw: FnPtrs) -> Dispatch {
+        Dispatch {
+            raw: raw,
+            suspended: UnsafeCell::new(0),
+        }
     }
 }
 
-pub fn attach_shader(program: GLuint, shader: GLuint) {
-    unsafe {
-        glAttachShader(program, shader);
-    }
+/// Keeps the `debug_gl` error check of a `Gl` suspended until dropped. Returned by
+/// `Gl::suspend_debug_checks`.
+pub struct DebugChecksSuspended<'a> {
+    gl: &'a Gl,
 }
 
-pub fn bind_attrib_location(program: GLuint, index: GLuint, name: &str) {
-    unsafe {
-        glBindAttribLocation(program, index, name.to_c_str().as_ptr());
+#[unsafe_destructor]
+impl<'a> Drop for DebugChecksSuspended<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            *self.gl.fns.suspended.get() -= 1;
+        }
     }
 }
 
-pub fn bind_buffer(target: GLenum, buffer: GLuint) {
-    unsafe {
-        glBindBuffer(target, buffer);
+/// The state of a generic vertex attribute, as reported by `get_vertex_attrib_state`.
+#[deriving(Clone, PartialEq, Show)]
+pub struct VertexAttribState {
+    /// Whether the attribute reads from its array rather than `current_value`.
+    pub enabled: bool,
+    /// The number of components per vertex, 1 to 4.
+    pub size: GLint,
+    /// The byte stride between vertices, or 0 for tightly packed.
+    pub stride: GLsizei,
+    /// The component type, e.g. `FLOAT`.
+    pub ty: GLenum,
+    /// Whether fixed-point components are normalized.
+    pub normalized: bool,
+    /// The buffer the array is sourced from, or 0 for client memory.
+    pub buffer_binding: GLuint,
+    /// The byte offset into `buffer_binding` (or the client pointer) of the first vertex.
+    pub offset: uint,
+    /// The value used when the array is disabled.
+    pub current_value: [GLfloat, ..4],
+}
+
+/// A 16.16 fixed-point vertex attribute component.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct Fixed(pub GLfixed);
+
+mod private {
+    /// Keeps `AttribComponent` from being implemented outside this module.
+    pub trait Sealed {}
+
+    /// Keeps `IndexType` from being implemented outside this module.
+    pub trait SealedIndex {}
+}
+
+/// A type that can be a vertex attribute component in `vertex_attrib_pointer`.
+///
+/// This trait is sealed: it is implemented for exactly the component types OpenGL ES 2.0 accepts
+/// (plus `i32`, which only desktop OpenGL accepts) and cannot be implemented elsewhere.
+pub trait AttribComponent: private::Sealed {
+    /// The `GLenum` naming this component type. The argument is only used to select the impl.
+    fn gl_type(_: Option<Self>) -> GLenum;
+}
+
+macro_rules! attrib_component {
+    ($($ty:ty => $gl_type:ident),+) => {
+        $(impl private::Sealed for $ty {}
+
+        impl AttribComponent for $ty {
+            fn gl_type(_: Option<$ty>) -> GLenum {
+                $gl_type
+            }
+        })+
     }
 }
 
-pub fn bind_framebuffer(target: GLenum, framebuffer: GLuint) {
-    unsafe {
-        glBindFramebuffer(target, framebuffer);
-    }
+attrib_component! {
+    i8 => BYTE,
+    u8 => UNSIGNED_BYTE,
+    i16 => SHORT,
+    u16 => UNSIGNED_SHORT,
+    i32 => INT,
+    Fixed => FIXED,
+    f32 => FLOAT
 }
 
-pub fn bind_texture(target: GLenum, texture: GLuint) {
-    unsafe {
-        glBindTexture(target, texture);
-    }
+/// A type that can be an element index in `draw_elements_indexed` and friends.
+///
+/// This trait is sealed: it is implemented for `u8`, `u16` and `u32`. OpenGL ES 2.0 only accepts
+/// `u32` indices with the `OES_element_index_uint` extension.
+pub trait IndexType: private::SealedIndex {
+    /// The `GLenum` naming this index type. The argument is only used to select the impl.
+    fn gl_type(_: Option<Self>) -> GLenum;
 }
 
-#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
-pub fn bind_vertex_array(array: GLuint) {
-    unsafe {
-        glBindVertexArray(array);
-    }
-}
+macro_rules! index_type {
+    ($($ty:ty => $gl_type:ident),+) => {
+        $(impl private::SealedIndex for $ty {}
 
-pub fn blend_color(red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf) {
-    unsafe {
-        glBlendColor(red, green, blue, alpha);
+        impl IndexType for $ty {
+            fn gl_type(_: Option<$ty>) -> GLenum {
+                $gl_type
+            }
+        })+
     }
 }
 
-pub fn blend_equation(mode: GLenum) {
-    unsafe {
-        glBlendEquation(mode);
-    }
+index_type! {
+    u8 => UNSIGNED_BYTE,
+    u16 => UNSIGNED_SHORT,
+    u32 => UNSIGNED_INT
 }
 
-pub fn blend_equation_separate(mode_rgb: GLenum, mode_alpha: GLenum) {
-    unsafe {
-        glBlendEquationSeparate(mode_rgb, mode_alpha);
-    }
+// Returns the number of whole indices of type `element_type` in `len` bytes.
+fn index_count(len: uint, element_type: GLenum) -> GLsizei {
+    let size = match element_type {
+        UNSIGNED_SHORT => 2,
+        UNSIGNED_INT => 4,
+        _ => 1,
+    };
+    (len / size) as GLsizei
 }
 
-pub fn blend_func(sfactor: GLenum, dfactor: GLenum) {
-    unsafe {
-        glBlendFunc(sfactor, dfactor);
+// Checks that `count` indices can be read from a slice of `len` of them.
+fn check_index_count(count: GLsizei, len: uint) -> Result<(), IndexCountError> {
+    if count < 0 {
+        return Err(IndexCountError::Negative(count))
     }
+    if count as uint > len {
+        return Err(IndexCountError::TooMany {
+            count: count,
+            len: len,
+        })
+    }
+    Ok(())
 }
 
-pub fn blend_func_separate(src_rgb: GLenum, dst_rgb: GLenum, src_alpha: GLenum, dst_alpha: GLenum) {
-    unsafe {
-        glBlendFuncSeparate(src_rgb, dst_rgb, src_alpha, dst_alpha);
+// Checks that `count` indices of type `T` can be read `offset` bytes into the bound
+// `ELEMENT_ARRAY_BUFFER`.
+fn check_index_offset<T: IndexType>(count: GLsizei, offset: uint) -> Result<(), IndexCountError> {
+    if count < 0 {
+        return Err(IndexCountError::Negative(count))
     }
-}
-// FIXME: There should be some type-safe wrapper for this...
-#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
-pub fn buffer_data<T>(target: GLenum, data: &[T], usage: GLenum) {
-    unsafe {
-        glBufferData(target,
-                         (data.len() * size_of::<T>()) as GLsizeiptr,
-                         data.as_ptr() as *const GLvoid,
-                         usage);
+    if offset % size_of::<T>() != 0 {
+        return Err(IndexCountError::MisalignedOffset {
+            offset: offset,
+            index_size: size_of::<T>(),
+        })
     }
+    Ok(())
 }
 
-// FIXME: There should be some type-safe wrapper for this...
-// FIXME: T is not working
-#[cfg(target_os="android")]
-pub fn buffer_data(target: GLenum, data: &[f32], usage: GLenum) {
-    unsafe {
-        glBufferData(target,
-                         (data.len() * size_of::<f32>()) as GLsizeiptr,
-                         data.as_ptr() as *const GLvoid,
-                         usage);
+// Checks that `len` values are enough for the texture parameter `pname`. Parameters not known
+// here, such as those of extensions, may have up to four values, the most any has.
+fn check_tex_parameter_count(pname: GLenum, len: uint) -> Result<(), ParameterError> {
+    let required = match pname {
+        TEXTURE_MIN_FILTER | TEXTURE_MAG_FILTER | TEXTURE_WRAP_S | TEXTURE_WRAP_T |
+        TEXTURE_STORAGE_HINT_APPLE => 1,
+        _ => 4,
+    };
+    if len < required {
+        return Err(ParameterError {
+            pname: pname,
+            required: required,
+            actual: len,
+        })
+    }
+    Ok(())
+}
+
+// Returns the number of `components`-sized elements in a uniform upload of `len` values.
+fn uniform_count(len: uint, components: uint) -> Result<GLsizei, UniformLengthError> {
+    if len % components != 0 {
+        return Err(UniformLengthError {
+            len: len,
+            components: components,
+        })
+    }
+    Ok((len / components) as GLsizei)
+}
+
+impl Gl {
+    /// Loads every entry point by name with `loadfn`.
+    pub fn load_with(loadfn: |&str| -> *const c_void) -> Gl {
+        Gl {
+            fns: Dispatch::new(FnPtrs::load_with(loadfn)),
+            pixel_store: UnsafeCell::new(INITIAL_PIXEL_STORE),
+            has_unpack_row_length: UnsafeCell::new(None),
+        }
     }
-}
 
-// FIXME: As above
-// Note: offset is the element offset index, not byte offset
-pub fn buffer_sub_data<T>(target: GLenum, element_offset_index: uint, data: &[T]) {
-    unsafe {
-        let size = size_of::<T>();
-        glBufferSubData(target,
-                        (element_offset_index * size) as GLintptr,
-                        (data.len() * size) as GLsizeiptr,
-                        data.as_ptr() as *const GLvoid);
+    /// Uses the symbols of the statically linked GL library.
+    #[cfg(feature = "static_link")]
+    pub fn load_static() -> Gl {
+        Gl {
+            fns: Dispatch::new(FnPtrs::load_static()),
+            pixel_store: UnsafeCell::new(INITIAL_PIXEL_STORE),
+            has_unpack_row_length: UnsafeCell::new(None),
+        }
     }
-}
 
-pub fn check_framebuffer_status(target: GLenum) -> GLenum {
-    unsafe {
-        glCheckFramebufferStatus(target)
+    /// The raw entry points of this context. Calls made through these are never checked by the
+    /// `debug_gl` feature.
+    pub fn fns(&self) -> &FnPtrs {
+        &self.fns.raw
     }
-}
 
-pub fn clear(mask: GLbitfield) {
-    unsafe {
-        glClear(mask);
+    /// Suspends the `debug_gl` check of `glGetError` after each call until the returned guard
+    /// is dropped, leaving errors queued for the caller to read. Without the `debug_gl` feature
+    /// this does nothing.
+    pub fn suspend_debug_checks(&self) -> DebugChecksSuspended {
+        unsafe {
+            *self.fns.suspended.get() += 1;
+        }
+        DebugChecksSuspended {
+            gl: self,
+        }
     }
-}
 
-pub fn clear_color(red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf) {
-    unsafe {
-        glClearColor(red, green, blue, alpha);
+    pub fn active_texture(&self, texture: GLenum) {
+        unsafe {
+            self.fns.glActiveTexture(texture);
+        }
     }
-}
 
-pub fn compile_shader(shader: GLuint) {
-    unsafe {
-        glCompileShader(shader);
+    pub fn attach_shader(&self, program: GLuint, shader: GLuint) {
+        unsafe {
+            self.fns.glAttachShader(program, shader);
+        }
     }
-}
 
-pub fn create_program() -> GLuint {
-    unsafe {
-        return glCreateProgram();
+    pub fn bind_attrib_location(&self, program: GLuint, index: GLuint, name: &str) {
+        unsafe {
+            self.fns.glBindAttribLocation(program, index, name.to_c_str().as_ptr());
+        }
     }
-}
 
-pub fn create_shader(shader_type: GLenum) -> GLuint {
-    unsafe {
-        return glCreateShader(shader_type);
+    pub fn bind_buffer(&self, target: GLenum, buffer: GLuint) {
+        unsafe {
+            self.fns.glBindBuffer(target, buffer);
+        }
     }
-}
 
-pub fn cull_face(mode: GLenum) {
-    unsafe {
-        glCullFace(mode);
+    pub fn bind_framebuffer(&self, target: GLenum, framebuffer: GLuint) {
+        unsafe {
+            self.fns.glBindFramebuffer(target, framebuffer);
+        }
     }
-}
 
-pub fn delete_buffers(buffers: &[GLuint]) {
-    unsafe {
-        glDeleteBuffers(buffers.len() as GLsizei, buffers.as_ptr());
+    pub fn bind_renderbuffer(&self, target: GLenum, renderbuffer: GLuint) {
+        unsafe {
+            self.fns.glBindRenderbuffer(target, renderbuffer);
+        }
     }
-}
 
-pub fn delete_frame_buffers(frame_buffers: &[GLuint]) {
-    unsafe {
-        glDeleteFramebuffers(frame_buffers.len() as GLsizei, frame_buffers.as_ptr());
+    pub fn bind_texture(&self, target: GLenum, texture: GLuint) {
+        unsafe {
+            self.fns.glBindTexture(target, texture);
+        }
     }
-}
 
-pub fn delete_program(program: GLuint) {
-    unsafe {
-        glDeleteProgram(program);
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    pub fn bind_vertex_array(&self, array: GLuint) {
+        unsafe {
+            self.fns.glBindVertexArray(array);
+        }
     }
-}
 
-pub fn delete_render_buffers(render_buffers: &[GLuint]) {
-    unsafe {
-        glDeleteRenderbuffers(render_buffers.len() as GLsizei, render_buffers.as_ptr());
+    pub fn blend_color(&self, red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf) {
+        unsafe {
+            self.fns.glBlendColor(red, green, blue, alpha);
+        }
     }
-}
 
-pub fn delete_shader(shader: GLuint) {
-    unsafe {
-        glDeleteShader(shader);
+    pub fn blend_equation(&self, mode: GLenum) {
+        unsafe {
+            self.fns.glBlendEquation(mode);
+        }
     }
-}
 
-pub fn delete_textures(textures: &[GLuint]) {
-    unsafe {
-        return glDeleteTextures(textures.len() as GLsizei, textures.as_ptr());
+    pub fn blend_equation_separate(&self, mode_rgb: GLenum, mode_alpha: GLenum) {
+        unsafe {
+            self.fns.glBlendEquationSeparate(mode_rgb, mode_alpha);
+        }
     }
-}
 
-pub fn depth_func(func: GLenum) {
-    unsafe {
-        glDepthFunc(func);
+    pub fn blend_func(&self, sfactor: GLenum, dfactor: GLenum) {
+        unsafe {
+            self.fns.glBlendFunc(sfactor, dfactor);
+        }
     }
-}
 
-pub fn depth_mask(flag: bool) {
-    unsafe {
-        glDepthMask(flag as GLboolean);
+    pub fn blend_func_separate(&self, src_rgb: GLenum, dst_rgb: GLenum, src_alpha: GLenum, dst_alpha: GLenum) {
+        unsafe {
+            self.fns.glBlendFuncSeparate(src_rgb, dst_rgb, src_alpha, dst_alpha);
+        }
     }
-}
 
-pub fn detach_shader(program: GLuint, shader: GLuint) {
-    unsafe {
-        glDetachShader(program, shader);
+    // FIXME: There should be some type-safe wrapper for this...
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    pub fn buffer_data<T>(&self, target: GLenum, data: &[T], usage: GLenum) {
+        unsafe {
+            self.fns.glBufferData(target,
+                             (data.len() * size_of::<T>()) as GLsizeiptr,
+                             data.as_ptr() as *const GLvoid,
+                             usage);
+        }
     }
-}
 
-pub fn draw_arrays(mode: GLenum, first: GLint, count: GLsizei) {
-    unsafe {
-        return glDrawArrays(mode, first, count);
+    // FIXME: There should be some type-safe wrapper for this...
+    // FIXME: T is not working
+    #[cfg(target_os="android")]
+    pub fn buffer_data(&self, target: GLenum, data: &[f32], usage: GLenum) {
+        unsafe {
+            self.fns.glBufferData(target,
+                             (data.len() * size_of::<f32>()) as GLsizeiptr,
+                             data.as_ptr() as *const GLvoid,
+                             usage);
+        }
     }
-}
 
-pub fn draw_elements(mode: GLenum, count: GLsizei, element_type: GLenum, indices: Option<&[u8]>) {
-    unsafe {
-        return glDrawElements(mode,
-                                  match indices {
-                                    Some(ref i) => cmp::min(count, i.len() as GLsizei),
-                                    None => count,
-                                  },
-                                  element_type,
-                                  match indices {
-                                    Some(ref i) => mem::transmute(&i[0]),
-                                    None => ptr::null(),
-                                  })
+    // FIXME: As above
+    // Note: offset is the element offset index, not byte offset
+    pub fn buffer_sub_data<T>(&self, target: GLenum, element_offset_index: uint, data: &[T]) {
+        unsafe {
+            let size = size_of::<T>();
+            self.fns.glBufferSubData(target,
+                            (element_offset_index * size) as GLintptr,
+                            (data.len() * size) as GLsizeiptr,
+                            data.as_ptr() as *const GLvoid);
+        }
     }
-}
 
+    pub fn check_framebuffer_status(&self, target: GLenum) -> GLenum {
+        unsafe {
+            self.fns.glCheckFramebufferStatus(target)
+        }
+    }
 
-#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
-pub fn draw_arrays_instanced(mode: GLenum, first: GLint, count: GLsizei, primcount: GLsizei) {
-    unsafe {
-        glDrawArraysInstanced(mode, first, count, primcount);
+    pub fn clear(&self, mask: GLbitfield) {
+        unsafe {
+            self.fns.glClear(mask);
+        }
     }
-}
 
-#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
-pub fn draw_elements_instanced(mode: GLenum, count: GLsizei, element_type: GLenum, indices: Option<&[u8]>, primcount: GLsizei) {
-    unsafe {
-        glDrawElementsInstanced(mode,
-                                    match indices {
-                                      Some(ref i) => cmp::min(count, i.len() as GLsizei),
-                                      None => count,
-                                    },
-                                    element_type,
-                                    match indices {
-                                      Some(ref i) => mem::transmute(&i[0]),
-                                      None => ptr::null(),
-                                    }, 
-                                    primcount);
+    pub fn clear_color(&self, red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf) {
+        unsafe {
+            self.fns.glClearColor(red, green, blue, alpha);
+        }
     }
-}
 
-pub fn enable(cap: GLenum) {
-    unsafe {
-        glEnable(cap);
+    pub fn clear_stencil(&self, s: GLint) {
+        unsafe {
+            self.fns.glClearStencil(s);
+        }
     }
-}
 
-pub fn disable(cap: GLenum) {
-    unsafe {
-        glDisable(cap);
+    pub fn compile_shader(&self, shader: GLuint) {
+        unsafe {
+            self.fns.glCompileShader(shader);
+        }
     }
-}
 
-pub fn enable_vertex_attrib_array(index: GLuint) {
-    unsafe {
-        glEnableVertexAttribArray(index);
+    /// Specifies a level of the texture bound to `target` from compressed `data`, which must be
+    /// exactly one `width` by `height` image in `internal_format`.
+    pub fn compressed_tex_image_2d(&self,
+                                   target: GLenum,
+                                   level: GLint,
+                                   internal_format: GLenum,
+                                   width: GLsizei,
+                                   height: GLsizei,
+                                   border: GLint,
+                                   data: &[u8])
+                                   -> Result<(), PixelError> {
+        try!(compressed::check_image_size(internal_format, width, height, data));
+        unsafe {
+            self.fns.glCompressedTexImage2D(target, level, internal_format, width, height, border,
+                                            data.len() as GLsizei, data.as_ptr() as *const GLvoid);
+        }
+        Ok(())
+    }
+
+    /// Replaces a region of a level of the texture bound to `target` with compressed `data`, which
+    /// must be exactly one `width` by `height` image in `format`. `ETC1_RGB8_OES` textures cannot
+    /// be updated in part.
+    pub fn compressed_tex_sub_image_2d(&self,
+                                       target: GLenum,
+                                       level: GLint,
+                                       xoffset: GLint,
+                                       yoffset: GLint,
+                                       width: GLsizei,
+                                       height: GLsizei,
+                                       format: GLenum,
+                                       data: &[u8])
+                                       -> Result<(), PixelError> {
+        // OES_compressed_ETC1_RGB8_texture only allows whole images to be specified.
+        if format == ETC1_RGB8_OES {
+            return Err(PixelError::UnsupportedFormat(format))
+        }
+        try!(compressed::check_image_size(format, width, height, data));
+        unsafe {
+            self.fns.glCompressedTexSubImage2D(target, level, xoffset, yoffset, width, height, format,
+                                               data.len() as GLsizei, data.as_ptr() as *const GLvoid);
+        }
+        Ok(())
+    }
+
+    pub fn copy_tex_image_2d(&self,
+                             target: GLenum,
+                             level: GLint,
+                             internal_format: GLenum,
+                             x: GLint,
+                             y: GLint,
+                             width: GLsizei,
+                             height: GLsizei,
+                             border: GLint) {
+        unsafe {
+            self.fns.glCopyTexImage2D(target, level, internal_format, x, y, width, height, border);
+        }
     }
-}
 
-pub fn disable_vertex_attrib_array(index: GLuint) {
-    unsafe {
-        glDisableVertexAttribArray(index);
+    pub fn copy_tex_sub_image_2d(&self,
+                                 target: GLenum,
+                                 level: GLint,
+                                 xoffset: GLint,
+                                 yoffset: GLint,
+                                 x: GLint,
+                                 y: GLint,
+                                 width: GLsizei,
+                                 height: GLsizei) {
+        unsafe {
+            self.fns.glCopyTexSubImage2D(target, level, xoffset, yoffset, x, y, width, height);
+        }
     }
-}
 
-pub fn finish() {
-    unsafe {
-        return glFinish();
+    pub fn create_program(&self) -> GLuint {
+        unsafe {
+            return self.fns.glCreateProgram();
+        }
     }
-}
 
-pub fn flush() {
-    unsafe {
-        return glFlush();
+    pub fn create_shader(&self, shader_type: GLenum) -> GLuint {
+        unsafe {
+            return self.fns.glCreateShader(shader_type);
+        }
     }
-}
 
-pub fn framebuffer_texture_2d(target: GLenum,
-                              attachment: GLenum,
-                              textarget: GLenum,
-                              texture: GLuint,
-                              level: GLint) {
-    unsafe {
-        glFramebufferTexture2D(target, attachment, textarget, texture, level);
+    pub fn cull_face(&self, mode: GLenum) {
+        unsafe {
+            self.fns.glCullFace(mode);
+        }
     }
-}
 
-pub fn front_face(mode: GLenum) {
-    unsafe {
-        glFrontFace(mode);
+    pub fn delete_buffers(&self, buffers: &[GLuint]) {
+        unsafe {
+            self.fns.glDeleteBuffers(buffers.len() as GLsizei, buffers.as_ptr());
+        }
     }
-}
 
-pub fn gen_buffers(n: GLsizei) -> Vec<GLuint> {
-    unsafe {
-        let mut result = Vec::from_elem(n as uint, 0 as GLuint);
-        glGenBuffers(n, result.as_mut_ptr());
-        return result;
+    pub fn delete_frame_buffers(&self, frame_buffers: &[GLuint]) {
+        unsafe {
+            self.fns.glDeleteFramebuffers(frame_buffers.len() as GLsizei, frame_buffers.as_ptr());
+        }
     }
-}
 
-pub fn gen_framebuffers(n: GLsizei) -> Vec<GLuint> {
-    unsafe {
-        let mut result = Vec::from_elem(n as uint, 0 as GLuint);
-        glGenFramebuffers(n, result.as_mut_ptr());
-        return result;
+    pub fn delete_program(&self, program: GLuint) {
+        unsafe {
+            self.fns.glDeleteProgram(program);
+        }
     }
-}
 
-pub fn gen_textures(n: GLsizei) -> Vec<GLuint> {
-    unsafe {
-        let mut result = Vec::from_elem(n as uint, 0 as GLuint);
-        glGenTextures(n, result.as_mut_ptr());
-        return result;
+    pub fn delete_render_buffers(&self, render_buffers: &[GLuint]) {
+        unsafe {
+            self.fns.glDeleteRenderbuffers(render_buffers.len() as GLsizei, render_buffers.as_ptr());
+        }
     }
-}
 
-#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
-pub fn gen_vertex_arrays(n: GLsizei) -> Vec<GLuint> {
-    unsafe {
-        let mut result = Vec::from_elem(n as uint, 0 as GLuint);
-        glGenVertexArrays(n, result.as_mut_ptr());
-        return result;
+    pub fn delete_shader(&self, shader: GLuint) {
+        unsafe {
+            self.fns.glDeleteShader(shader);
+        }
     }
-}
 
-pub fn get_attrib_location(program: GLuint, name: &str) -> c_int {
-    unsafe {
-        glGetAttribLocation(program, name.to_c_str().as_ptr() as *const GLchar)
+    pub fn delete_textures(&self, textures: &[GLuint]) {
+        unsafe {
+            return self.fns.glDeleteTextures(textures.len() as GLsizei, textures.as_ptr());
+        }
     }
-}
 
-pub fn get_error() -> GLenum {
-    unsafe {
-        return glGetError();
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    pub fn delete_vertex_arrays(&self, arrays: &[GLuint]) {
+        unsafe {
+            self.fns.glDeleteVertexArrays(arrays.len() as GLsizei, arrays.as_ptr());
+        }
     }
-}
 
-pub fn get_integer_v(pname: GLenum, result: &mut [GLint]) {
-    unsafe {
-        glGetIntegerv(pname, &mut result[0]);
+    pub fn depth_func(&self, func: GLenum) {
+        unsafe {
+            self.fns.glDepthFunc(func);
+        }
     }
-}
 
-pub fn get_program_info_log(program: GLuint) -> String {
-    unsafe {
-        let mut result = Vec::from_elem(1024u, 0u8);
-        let mut result_len: GLsizei = 0 as GLsizei;
-        glGetProgramInfoLog(program,
-                            1024 as GLsizei,
-                            &mut result_len,
-                            result.as_ptr() as *mut GLchar);
-        result.truncate(if result_len > 0 {result_len as uint - 1u} else {0u});
-        from_utf8(result.as_slice()).unwrap().to_string()
+    pub fn depth_mask(&self, flag: bool) {
+        unsafe {
+            self.fns.glDepthMask(flag as GLboolean);
+        }
     }
-}
 
-pub fn get_program_iv(program: GLuint, pname: GLenum) -> GLint {
-    unsafe {
-        let mut result: GLint = 0 as GLint;
-        glGetProgramiv(program, pname, &mut result);
-        return result;
+    pub fn detach_shader(&self, program: GLuint, shader: GLuint) {
+        unsafe {
+            self.fns.glDetachShader(program, shader);
+        }
     }
-}
 
-pub fn get_shader_info_log(shader: GLuint) -> String {
-    unsafe {
-        let mut result = Vec::from_elem(1024u, 0u8);
-        let mut result_len: GLsizei = 0 as GLsizei;
-        glGetShaderInfoLog(shader,
-                           1024 as GLsizei,
-                           &mut result_len,
-                           result.as_ptr() as *mut GLchar);
-        result.truncate(if result_len > 0 {result_len as uint - 1u} else {0u});
-        from_utf8(result.as_slice()).unwrap().to_string()
+    pub fn draw_arrays(&self, mode: GLenum, first: GLint, count: GLsizei) {
+        unsafe {
+            return self.fns.glDrawArrays(mode, first, count);
+        }
     }
-}
 
-pub fn get_string(which: GLenum) -> String {
-    unsafe {
-        let llstr = glGetString(which);
-        if !llstr.is_null() {
-            return string::raw::from_buf(llstr as *const u8);
-        } else {
-            return "".to_string();
+    /// Draws from `indices`, which are bytes holding indices of type `element_type`. `count` is
+    /// clamped to the number of whole indices in the slice.
+    pub fn draw_elements(&self, mode: GLenum, count: GLsizei, element_type: GLenum, indices: Option<&[u8]>) {
+        unsafe {
+            return self.fns.glDrawElements(mode,
+                                      match indices {
+                                        Some(ref i) => cmp::min(count, index_count(i.len(), element_type)),
+                                        None => count,
+                                      },
+                                      element_type,
+                                      match indices {
+                                        Some(ref i) => i.as_ptr() as *const GLvoid,
+                                        None => ptr::null(),
+                                      })
         }
     }
-}
 
-pub fn get_shader_iv(shader: GLuint, pname: GLenum) -> GLint {
-    unsafe {
-        let mut result: GLint = 0 as GLint;
-        glGetShaderiv(shader, pname, &mut result);
-        return result;
+    /// Draws the first `count` of `indices`. Returns an error without drawing if `count` is
+    /// negative or exceeds the length of `indices`.
+    pub fn draw_elements_indexed<T: IndexType>(&self, mode: GLenum, count: GLsizei, indices: &[T])
+                                               -> Result<(), IndexCountError> {
+        try!(check_index_count(count, indices.len()));
+        unsafe {
+            self.fns.glDrawElements(mode,
+                                    count,
+                                    IndexType::gl_type(None::<T>),
+                                    indices.as_ptr() as *const GLvoid);
+        }
+        Ok(())
     }
-}
 
-pub fn get_uniform_location(program: GLuint, name: &str) -> c_int {
-    unsafe {
-        glGetUniformLocation(program, name.to_c_str().as_ptr() as *const GLchar)
+    /// Draws `count` indices of type `T` starting `offset` bytes into the bound
+    /// `ELEMENT_ARRAY_BUFFER`. Returns an error without drawing if `count` is negative or
+    /// `offset` is not a multiple of the size of `T`.
+    pub fn draw_elements_offset<T: IndexType>(&self, mode: GLenum, count: GLsizei, offset: uint)
+                                              -> Result<(), IndexCountError> {
+        try!(check_index_offset::<T>(count, offset));
+        unsafe {
+            self.fns.glDrawElements(mode, count, IndexType::gl_type(None::<T>), offset as *const GLvoid);
+        }
+        Ok(())
     }
-}
 
-pub fn is_buffer(buffer: GLuint) -> bool {
-  unsafe {
-    glIsBuffer(buffer) > 0
-  }
-}
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    pub fn draw_arrays_instanced(&self, mode: GLenum, first: GLint, count: GLsizei, primcount: GLsizei) {
+        unsafe {
+            self.fns.glDrawArraysInstanced(mode, first, count, primcount);
+        }
+    }
 
-pub fn is_enabled(cap: GLenum) -> bool {
-  unsafe {
-    glIsEnabled(cap) > 0
-  }
-}
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    pub fn draw_elements_instanced(&self, mode: GLenum, count: GLsizei, element_type: GLenum, indices: Option<&[u8]>, primcount: GLsizei) {
+        unsafe {
+            self.fns.glDrawElementsInstanced(mode,
+                                        match indices {
+                                          Some(ref i) => cmp::min(count, index_count(i.len(), element_type)),
+                                          None => count,
+                                        },
+                                        element_type,
+                                        match indices {
+                                          Some(ref i) => i.as_ptr() as *const GLvoid,
+                                          None => ptr::null(),
+                                        },
+                                        primcount);
+        }
+    }
 
-pub fn is_framebuffer(framebuffer: GLuint) -> bool {
-  unsafe {
-    glIsFramebuffer(framebuffer) > 0
-  }
-}
+    /// Draws `primcount` instances of the first `count` of `indices`. Returns an error without
+    /// drawing if `count` is negative or exceeds the length of `indices`.
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    pub fn draw_elements_instanced_indexed<T: IndexType>(&self,
+                                                         mode: GLenum,
+                                                         count: GLsizei,
+                                                         indices: &[T],
+                                                         primcount: GLsizei)
+                                                         -> Result<(), IndexCountError> {
+        try!(check_index_count(count, indices.len()));
+        unsafe {
+            self.fns.glDrawElementsInstanced(mode,
+                                             count,
+                                             IndexType::gl_type(None::<T>),
+                                             indices.as_ptr() as *const GLvoid,
+                                             primcount);
+        }
+        Ok(())
+    }
+
+    /// Draws `primcount` instances of `count` indices of type `T` starting `offset` bytes into
+    /// the bound `ELEMENT_ARRAY_BUFFER`. Returns an error without drawing if `count` is negative
+    /// or `offset` is not a multiple of the size of `T`.
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    pub fn draw_elements_instanced_offset<T: IndexType>(&self,
+                                                        mode: GLenum,
+                                                        count: GLsizei,
+                                                        offset: uint,
+                                                        primcount: GLsizei)
+                                                        -> Result<(), IndexCountError> {
+        try!(check_index_offset::<T>(count, offset));
+        unsafe {
+            self.fns.glDrawElementsInstanced(mode,
+                                             count,
+                                             IndexType::gl_type(None::<T>),
+                                             offset as *const GLvoid,
+                                             primcount);
+        }
+        Ok(())
+    }
 
-pub fn is_program(program: GLuint) -> bool {
-  unsafe {
-    glIsProgram(program) > 0
-  }
-}
+    pub fn enable(&self, cap: GLenum) {
+        unsafe {
+            self.fns.glEnable(cap);
+        }
+    }
 
-pub fn is_renderbuffer(renderbuffer: GLuint) -> bool {
-  unsafe {
-    glIsRenderbuffer(renderbuffer) > 0
-  }
-}
+    pub fn disable(&self, cap: GLenum) {
+        unsafe {
+            self.fns.glDisable(cap);
+        }
+    }
+
+    pub fn enable_vertex_attrib_array(&self, index: GLuint) {
+        unsafe {
+            self.fns.glEnableVertexAttribArray(index);
+        }
+    }
+
+    pub fn disable_vertex_attrib_array(&self, index: GLuint) {
+        unsafe {
+            self.fns.glDisableVertexAttribArray(index);
+        }
+    }
+
+    pub fn finish(&self) {
+        unsafe {
+            return self.fns.glFinish();
+        }
+    }
+
+    pub fn flush(&self) {
+        unsafe {
+            return self.fns.glFlush();
+        }
+    }
+
+    pub fn framebuffer_renderbuffer(&self,
+                                    target: GLenum,
+                                    attachment: GLenum,
+                                    renderbuffertarget: GLenum,
+                                    renderbuffer: GLuint) {
+        unsafe {
+            self.fns.glFramebufferRenderbuffer(target, attachment, renderbuffertarget, renderbuffer);
+        }
+    }
+
+    pub fn framebuffer_texture_2d(&self,
+                                  target: GLenum,
+                                  attachment: GLenum,
+                                  textarget: GLenum,
+                                  texture: GLuint,
+                                  level: GLint) {
+        unsafe {
+            self.fns.glFramebufferTexture2D(target, attachment, textarget, texture, level);
+        }
+    }
+
+    pub fn front_face(&self, mode: GLenum) {
+        unsafe {
+            self.fns.glFrontFace(mode);
+        }
+    }
+
+    pub fn gen_buffers(&self, n: GLsizei) -> Vec<GLuint> {
+        unsafe {
+            let mut result = Vec::from_elem(n as uint, 0 as GLuint);
+            self.fns.glGenBuffers(n, result.as_mut_ptr());
+            return result;
+        }
+    }
+
+    pub fn gen_framebuffers(&self, n: GLsizei) -> Vec<GLuint> {
+        unsafe {
+            let mut result = Vec::from_elem(n as uint, 0 as GLuint);
+            self.fns.glGenFramebuffers(n, result.as_mut_ptr());
+            return result;
+        }
+    }
+
+    pub fn gen_renderbuffers(&self, n: GLsizei) -> Vec<GLuint> {
+        unsafe {
+            let mut result = Vec::from_elem(n as uint, 0 as GLuint);
+            self.fns.glGenRenderbuffers(n, result.as_mut_ptr());
+            return result;
+        }
+    }
+
+    pub fn gen_textures(&self, n: GLsizei) -> Vec<GLuint> {
+        unsafe {
+            let mut result = Vec::from_elem(n as uint, 0 as GLuint);
+            self.fns.glGenTextures(n, result.as_mut_ptr());
+            return result;
+        }
+    }
+
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    pub fn gen_vertex_arrays(&self, n: GLsizei) -> Vec<GLuint> {
+        unsafe {
+            let mut result = Vec::from_elem(n as uint, 0 as GLuint);
+            self.fns.glGenVertexArrays(n, result.as_mut_ptr());
+            return result;
+        }
+    }
+
+    pub fn generate_mipmap(&self, target: GLenum) {
+        unsafe {
+            self.fns.glGenerateMipmap(target);
+        }
+    }
+
+    pub fn get_active_attrib(&self, program: GLuint, index: GLuint) -> ActiveInfo {
+        let buf_size = cmp::max(self.get_program_iv(program, ACTIVE_ATTRIBUTE_MAX_LENGTH), 1);
+        unsafe {
+            let mut name = Vec::from_elem(buf_size as uint, 0u8);
+            let mut length: GLsizei = 0 as GLsizei;
+            let mut size: GLint = 0 as GLint;
+            let mut ty: GLenum = 0 as GLenum;
+            self.fns.glGetActiveAttrib(program, index, buf_size, &mut length, &mut size, &mut ty,
+                                       name.as_mut_ptr() as *mut GLchar);
+            name.truncate(length as uint);
+            ActiveInfo {
+                name: from_utf8(name.as_slice()).unwrap().to_string(),
+                size: size,
+                ty: ty,
+            }
+        }
+    }
+
+    pub fn get_active_uniform(&self, program: GLuint, index: GLuint) -> ActiveInfo {
+        let buf_size = cmp::max(self.get_program_iv(program, ACTIVE_UNIFORM_MAX_LENGTH), 1);
+        unsafe {
+            let mut name = Vec::from_elem(buf_size as uint, 0u8);
+            let mut length: GLsizei = 0 as GLsizei;
+            let mut size: GLint = 0 as GLint;
+            let mut ty: GLenum = 0 as GLenum;
+            self.fns.glGetActiveUniform(program, index, buf_size, &mut length, &mut size, &mut ty,
+                                        name.as_mut_ptr() as *mut GLchar);
+            name.truncate(length as uint);
+            ActiveInfo {
+                name: from_utf8(name.as_slice()).unwrap().to_string(),
+                size: size,
+                ty: ty,
+            }
+        }
+    }
+
+    pub fn get_attached_shaders(&self, program: GLuint) -> Vec<GLuint> {
+        let max_count = self.get_program_iv(program, ATTACHED_SHADERS);
+        unsafe {
+            let mut result = Vec::from_elem(max_count as uint, 0 as GLuint);
+            let mut count: GLsizei = 0 as GLsizei;
+            self.fns.glGetAttachedShaders(program, max_count, &mut count, result.as_mut_ptr());
+            result.truncate(count as uint);
+            result
+        }
+    }
+
+    pub fn get_attrib_location(&self, program: GLuint, name: &str) -> c_int {
+        unsafe {
+            self.fns.glGetAttribLocation(program, name.to_c_str().as_ptr() as *const GLchar)
+        }
+    }
+
+    /// Returns the compressed texture formats the context accepts.
+    pub fn get_compressed_texture_formats(&self) -> Vec<GLenum> {
+        let mut count = [0];
+        self.get_integer_v(NUM_COMPRESSED_TEXTURE_FORMATS, count.as_mut_slice());
+        if count[0] <= 0 {
+            return Vec::new()
+        }
+        let mut formats = Vec::from_elem(count[0] as uint, 0 as GLint);
+        self.get_integer_v(COMPRESSED_TEXTURE_FORMATS, formats.as_mut_slice());
+        formats.iter().map(|&format| format as GLenum).collect()
+    }
+
+    pub fn get_error(&self) -> GLenum {
+        unsafe {
+            return self.fns.glGetError();
+        }
+    }
+
+    pub fn get_framebuffer_attachment_parameter_iv(&self,
+                                                   target: GLenum,
+                                                   attachment: GLenum,
+                                                   pname: GLenum)
+                                                   -> GLint {
+        unsafe {
+            let mut result = 0 as GLint;
+            self.fns.glGetFramebufferAttachmentParameteriv(target, attachment, pname, &mut result);
+            result
+        }
+    }
+
+    pub fn get_integer_v(&self, pname: GLenum, result: &mut [GLint]) {
+        unsafe {
+            self.fns.glGetIntegerv(pname, &mut result[0]);
+        }
+    }
+
+    pub fn get_program_info_log(&self, program: GLuint) -> String {
+        unsafe {
+            let mut result = Vec::from_elem(1024u, 0u8);
+            let mut result_len: GLsizei = 0 as GLsizei;
+            self.fns.glGetProgramInfoLog(program,
+                                1024 as GLsizei,
+                                &mut result_len,
+                                result.as_ptr() as *mut GLchar);
+            result.truncate(if result_len > 0 {result_len as uint - 1u} else {0u});
+            from_utf8(result.as_slice()).unwrap().to_string()
+        }
+    }
+
+    pub fn get_program_iv(&self, program: GLuint, pname: GLenum) -> GLint {
+        unsafe {
+            let mut result: GLint = 0 as GLint;
+            self.fns.glGetProgramiv(program, pname, &mut result);
+            return result;
+        }
+    }
+
+    pub fn get_renderbuffer_parameter_iv(&self, target: GLenum, pname: GLenum) -> GLint {
+        unsafe {
+            let mut result: GLint = 0 as GLint;
+            self.fns.glGetRenderbufferParameteriv(target, pname, &mut result);
+            return result;
+        }
+    }
+
+    pub fn get_shader_info_log(&self, shader: GLuint) -> String {
+        unsafe {
+            let mut result = Vec::from_elem(1024u, 0u8);
+            let mut result_len: GLsizei = 0 as GLsizei;
+            self.fns.glGetShaderInfoLog(shader,
+                               1024 as GLsizei,
+                               &mut result_len,
+                               result.as_ptr() as *mut GLchar);
+            result.truncate(if result_len > 0 {result_len as uint - 1u} else {0u});
+            from_utf8(result.as_slice()).unwrap().to_string()
+        }
+    }
+
+    pub fn get_string(&self, which: GLenum) -> String {
+        unsafe {
+            let llstr = self.fns.glGetString(which);
+            if !llstr.is_null() {
+                return string::raw::from_buf(llstr as *const u8);
+            } else {
+                return "".to_string();
+            }
+        }
+    }
+
+    /// Returns the first value of the texture parameter `pname`.
+    pub fn get_tex_parameter_fv(&self, target: GLenum, pname: GLenum) -> GLfloat {
+        // Parameters such as `TEXTURE_BORDER_COLOR` on desktop GL write four values.
+        let mut result = [0.0 as GLfloat, ..4];
+        unsafe {
+            self.fns.glGetTexParameterfv(target, pname, result.as_mut_ptr());
+        }
+        result[0]
+    }
+
+    /// Returns the first value of the texture parameter `pname`.
+    pub fn get_tex_parameter_iv(&self, target: GLenum, pname: GLenum) -> GLint {
+        // Parameters such as `TEXTURE_BORDER_COLOR` on desktop GL write four values.
+        let mut result = [0 as GLint, ..4];
+        unsafe {
+            self.fns.glGetTexParameteriv(target, pname, result.as_mut_ptr());
+        }
+        result[0]
+    }
+
+    pub fn get_shader_iv(&self, shader: GLuint, pname: GLenum) -> GLint {
+        unsafe {
+            let mut result: GLint = 0 as GLint;
+            self.fns.glGetShaderiv(shader, pname, &mut result);
+            return result;
+        }
+    }
+
+    /// Reads up to `result.len()` components of the uniform at `location` as floats.
+    pub fn get_uniform_fv(&self, program: GLuint, location: GLint, result: &mut [GLfloat]) {
+        // The driver writes every component of the uniform, so read into a buffer large enough
+        // for a mat4 rather than trusting `result`.
+        let mut values = [0.0 as GLfloat, ..16];
+        unsafe {
+            self.fns.glGetUniformfv(program, location, values.as_mut_ptr());
+        }
+        for (dst, src) in result.iter_mut().zip(values.iter()) {
+            *dst = *src;
+        }
+    }
+
+    /// Reads up to `result.len()` components of the uniform at `location` as integers.
+    pub fn get_uniform_iv(&self, program: GLuint, location: GLint, result: &mut [GLint]) {
+        let mut values = [0 as GLint, ..16];
+        unsafe {
+            self.fns.glGetUniformiv(program, location, values.as_mut_ptr());
+        }
+        for (dst, src) in result.iter_mut().zip(values.iter()) {
+            *dst = *src;
+        }
+    }
+
+    pub fn get_uniform_location(&self, program: GLuint, name: &str) -> c_int {
+        unsafe {
+            self.fns.glGetUniformLocation(program, name.to_c_str().as_ptr() as *const GLchar)
+        }
+    }
+
+    /// Reads up to `result.len()` components of the vertex attribute parameter `pname`.
+    pub fn get_vertex_attrib_fv(&self, index: GLuint, pname: GLenum, result: &mut [GLfloat]) {
+        // `CURRENT_VERTEX_ATTRIB` writes four values, everything else one.
+        let mut values = [0.0 as GLfloat, ..4];
+        unsafe {
+            self.fns.glGetVertexAttribfv(index, pname, values.as_mut_ptr());
+        }
+        for (dst, src) in result.iter_mut().zip(values.iter()) {
+            *dst = *src;
+        }
+    }
+
+    /// Reads up to `result.len()` components of the vertex attribute parameter `pname`.
+    pub fn get_vertex_attrib_iv(&self, index: GLuint, pname: GLenum, result: &mut [GLint]) {
+        let mut values = [0 as GLint, ..4];
+        unsafe {
+            self.fns.glGetVertexAttribiv(index, pname, values.as_mut_ptr());
+        }
+        for (dst, src) in result.iter_mut().zip(values.iter()) {
+            *dst = *src;
+        }
+    }
+
+    /// Returns the `VERTEX_ATTRIB_ARRAY_POINTER` of the attribute, which is a byte offset when a
+    /// buffer is bound to it.
+    pub fn get_vertex_attrib_pointer_v(&self, index: GLuint, pname: GLenum) -> uint {
+        unsafe {
+            let mut result: *mut GLvoid = ptr::null_mut();
+            self.fns.glGetVertexAttribPointerv(index, pname, &mut result);
+            result as uint
+        }
+    }
+
+    pub fn get_vertex_attrib_state(&self, index: GLuint) -> VertexAttribState {
+        let get_i = |pname| {
+            let mut result = [0 as GLint];
+            self.get_vertex_attrib_iv(index, pname, result.as_mut_slice());
+            result[0]
+        };
+        let mut current_value = [0.0 as GLfloat, ..4];
+        self.get_vertex_attrib_fv(index, CURRENT_VERTEX_ATTRIB, current_value.as_mut_slice());
+        VertexAttribState {
+            enabled: get_i(VERTEX_ATTRIB_ARRAY_ENABLED) != 0,
+            size: get_i(VERTEX_ATTRIB_ARRAY_SIZE),
+            stride: get_i(VERTEX_ATTRIB_ARRAY_STRIDE),
+            ty: get_i(VERTEX_ATTRIB_ARRAY_TYPE) as GLenum,
+            normalized: get_i(VERTEX_ATTRIB_ARRAY_NORMALIZED) != 0,
+            buffer_binding: get_i(VERTEX_ATTRIB_ARRAY_BUFFER_BINDING) as GLuint,
+            offset: self.get_vertex_attrib_pointer_v(index, VERTEX_ATTRIB_ARRAY_POINTER),
+            current_value: current_value,
+        }
+    }
+
+    pub fn hint(&self, target: GLenum, mode: GLenum) {
+        unsafe {
+            self.fns.glHint(target, mode);
+        }
+    }
+
+    pub fn is_buffer(&self, buffer: GLuint) -> bool {
+      unsafe {
+        self.fns.glIsBuffer(buffer) > 0
+      }
+    }
+
+    pub fn is_enabled(&self, cap: GLenum) -> bool {
+      unsafe {
+        self.fns.glIsEnabled(cap) > 0
+      }
+    }
+
+    pub fn is_framebuffer(&self, framebuffer: GLuint) -> bool {
+      unsafe {
+        self.fns.glIsFramebuffer(framebuffer) > 0
+      }
+    }
+
+    pub fn is_program(&self, program: GLuint) -> bool {
+      unsafe {
+        self.fns.glIsProgram(program) > 0
+      }
+    }
+
+    pub fn is_renderbuffer(&self, renderbuffer: GLuint) -> bool {
+      unsafe {
+        self.fns.glIsRenderbuffer(renderbuffer) > 0
+      }
+    }
+
+    pub fn is_shader(&self, shader: GLuint) -> bool {
+      unsafe {
+        self.fns.glIsShader(shader) > 0
+      }
+    }
+
+    pub fn is_texture(&self, texture: GLuint) -> bool {
+      unsafe {
+        self.fns.glIsTexture(texture) > 0
+      }
+    }
+
+    pub fn line_width(&self, width: GLfloat) {
+      unsafe {
+        self.fns.glLineWidth(width);
+      }
+    }
+
+    pub fn link_program(&self, program: GLuint) {
+        unsafe {
+            return self.fns.glLinkProgram(program);
+        }
+    }
+
+    /// Sets a pixel-store parameter, remembering the layout parameters in `pixel_store`.
+    pub fn pixel_store_i(&self, pname: GLenum, param: GLint) {
+        unsafe {
+            self.fns.glPixelStorei(pname, param);
+        }
+        let store = unsafe { &mut *self.pixel_store.get() };
+        match (pname, param) {
+            (PACK_ALIGNMENT, 1) | (PACK_ALIGNMENT, 2) |
+            (PACK_ALIGNMENT, 4) | (PACK_ALIGNMENT, 8) => store.pack_alignment = param,
+            (UNPACK_ALIGNMENT, 1) | (UNPACK_ALIGNMENT, 2) |
+            (UNPACK_ALIGNMENT, 4) | (UNPACK_ALIGNMENT, 8) => store.unpack_alignment = param,
+            (UNPACK_ROW_LENGTH, _) if param >= 0 && self.has_unpack_row_length() => {
+                store.unpack_row_length = param
+            }
+            (UNPACK_CLIENT_STORAGE_APPLE, _) => store.unpack_client_storage_apple = param != 0,
+            // Anything else is not a layout parameter, or is rejected by GL without effect.
+            _ => {}
+        }
+    }
+
+    /// Returns the pixel-store state set through `pixel_store_i` and `set_pixel_store`, starting
+    /// from `INITIAL_PIXEL_STORE`. Changes made through the raw entry points or by other
+    /// libraries sharing the context are not seen until `refresh_pixel_store` is called.
+    pub fn pixel_store(&self) -> PixelStore {
+        unsafe {
+            (*self.pixel_store.get()).clone()
+        }
+    }
+
+    /// Queries the pixel-store state of the context, replacing what `pixel_store` remembers.
+    /// `unpack_row_length` is 0 on OpenGL ES 2.0 contexts without `EXT_unpack_subimage`, which
+    /// have no such parameter.
+    pub fn refresh_pixel_store(&self) -> PixelStore {
+        let pack_alignment = self.get_integer(PACK_ALIGNMENT);
+        let unpack_alignment = self.get_integer(UNPACK_ALIGNMENT);
+        let unpack_row_length = if self.has_unpack_row_length() {
+            self.get_integer(UNPACK_ROW_LENGTH)
+        } else {
+            0
+        };
+        let unpack_client_storage_apple = if cfg!(target_os="macos") {
+            self.get_integer(UNPACK_CLIENT_STORAGE_APPLE) != 0
+        } else {
+            false
+        };
+        let store = PixelStore {
+            pack_alignment: pack_alignment,
+            unpack_alignment: unpack_alignment,
+            unpack_row_length: unpack_row_length,
+            unpack_client_storage_apple: unpack_client_storage_apple,
+        };
+        unsafe {
+            *self.pixel_store.get() = store.clone();
+        }
+        store
+    }
+
+    // Whether the context has `UNPACK_ROW_LENGTH`, which OpenGL ES 2.0 only has with
+    // `EXT_unpack_subimage`. The strings are only checked once.
+    fn has_unpack_row_length(&self) -> bool {
+        if !cfg!(target_os="android") {
+            return true
+        }
+        unsafe {
+            match *self.has_unpack_row_length.get() {
+                Some(result) => return result,
+                None => {}
+            }
+        }
+        let result = self.get_string(VERSION).as_slice().starts_with("OpenGL ES 3") ||
+            self.get_string(EXTENSIONS).as_slice().split(' ').any(|extension| {
+                extension == "GL_EXT_unpack_subimage"
+            });
+        unsafe {
+            *self.has_unpack_row_length.get() = Some(result);
+        }
+        result
+    }
+
+    // Returns the single integer value of the state variable `pname`.
+    fn get_integer(&self, pname: GLenum) -> GLint {
+        let mut result = [0];
+        self.get_integer_v(pname, result.as_mut_slice());
+        result[0]
+    }
+
+    /// Sets every pixel-store parameter that differs from `store`.
+    pub fn set_pixel_store(&self, store: &PixelStore) {
+        let current = self.pixel_store();
+        if current.pack_alignment != store.pack_alignment {
+            self.pixel_store_i(PACK_ALIGNMENT, store.pack_alignment);
+        }
+        if current.unpack_alignment != store.unpack_alignment {
+            self.pixel_store_i(UNPACK_ALIGNMENT, store.unpack_alignment);
+        }
+        if current.unpack_row_length != store.unpack_row_length {
+            self.pixel_store_i(UNPACK_ROW_LENGTH, store.unpack_row_length);
+        }
+        if current.unpack_client_storage_apple != store.unpack_client_storage_apple {
+            self.pixel_store_i(UNPACK_CLIENT_STORAGE_APPLE,
+                               store.unpack_client_storage_apple as GLint);
+        }
+    }
+
+    /// Runs `f` with the pixel-store state set to `store`, then restores the previous state.
+    pub fn with_pixel_store<R>(&self, store: &PixelStore, f: || -> R) -> R {
+        let saved = self.pixel_store();
+        self.set_pixel_store(store);
+        let result = f();
+        self.set_pixel_store(&saved);
+        result
+    }
+
+    #[cfg(not(target_os = "android"))]
+    pub fn polygon_mode(&self, face: GLenum, mode: GLenum) {
+        unsafe {
+            self.fns.glPolygonMode(face, mode);
+        }
+    }
+
+    /// Reads a `width` by `height` rectangle of the bound framebuffer into a new buffer, with
+    /// tightly packed rows. `PACK_ALIGNMENT` is set to 1 for the read and restored afterwards.
+    pub fn read_pixels(&self, x: GLint, y: GLint, width: GLsizei, height: GLsizei, format: GLenum, pixel_type: GLenum)
+                       -> Result<Vec<u8>, PixelError> {
+        let mut store = self.pixel_store();
+        store.pack_alignment = 1;
+        self.with_pixel_store(&store, || {
+            let len = try!(self.read_pixels_size(width, height, format, pixel_type));
+            let mut pixels = Vec::from_elem(len, 0u8);
+            try!(self.read_pixels_into(x, y, width, height, format, pixel_type,
+                                       pixels.as_mut_slice()));
+            Ok(pixels)
+        })
+    }
+
+    /// Reads a `width` by `height` rectangle of the bound framebuffer into `pixels`, with rows
+    /// padded to the current `PACK_ALIGNMENT`. `format` must be `ALPHA`, `RGB`, `RGBA` or
+    /// `BGRA`.
+    pub fn read_pixels_into(&self,
+                            x: GLint,
+                            y: GLint,
+                            width: GLsizei,
+                            height: GLsizei,
+                            format: GLenum,
+                            pixel_type: GLenum,
+                            pixels: &mut [u8])
+                            -> Result<(), PixelError> {
+        let required = try!(self.read_pixels_size(width, height, format, pixel_type));
+        try!(pixels::check_buffer_size(pixels.len(), required));
+        unsafe {
+            self.fns.glReadPixels(x, y, width, height, format, pixel_type, pixels.as_mut_ptr() as *mut c_void);
+        }
+        Ok(())
+    }
+
+    // Returns the number of bytes `read_pixels` writes for the current `PACK_ALIGNMENT`.
+    fn read_pixels_size(&self, width: GLsizei, height: GLsizei, format: GLenum, pixel_type: GLenum)
+                        -> Result<uint, PixelError> {
+        match format {
+            ALPHA | RGB | RGBA | BGRA => {}
+            _ => return Err(PixelError::UnsupportedFormat(format)),
+        }
+        self.pixel_store().pack_size(width, height, format, pixel_type)
+    }
+
+    pub fn renderbuffer_storage(&self,
+                                target: GLenum,
+                                internalformat: GLenum,
+                                width: GLsizei,
+                                height: GLsizei) {
+        unsafe {
+            self.fns.glRenderbufferStorage(target, internalformat, width, height);
+        }
+    }
+
+    pub fn scissor(&self, x: GLint, y: GLint, width: GLsizei, height: GLsizei) {
+        unsafe {
+            self.fns.glScissor(x, y, width, height);
+        }
+    }
+
+    pub fn shader_source(&self, shader: GLuint, strings: &[&[u8]]) {
+        let pointers: Vec<*const u8> = strings.iter().map(|string| (*string).as_ptr()).collect();
+        let lengths: Vec<GLint> = strings.iter().map(|string| string.len() as GLint).collect();
+        unsafe {
+            self.fns.glShaderSource(shader, pointers.len() as GLsizei,
+                           pointers.as_ptr() as *const *const GLchar, lengths.as_ptr());
+        }
+        drop(lengths);
+        drop(pointers);
+    }
+
+    pub fn stencil_func(&self, func: GLenum, reference: GLint, mask: GLuint) {
+        unsafe {
+            self.fns.glStencilFunc(func, reference, mask);
+        }
+    }
+
+    pub fn stencil_func_separate(&self, face: GLenum, func: GLenum, reference: GLint, mask: GLuint) {
+        unsafe {
+            self.fns.glStencilFuncSeparate(face, func, reference, mask);
+        }
+    }
+
+    pub fn stencil_mask(&self, mask: GLuint) {
+        unsafe {
+            self.fns.glStencilMask(mask);
+        }
+    }
+
+    pub fn stencil_mask_separate(&self, face: GLenum, mask: GLuint) {
+        unsafe {
+            self.fns.glStencilMaskSeparate(face, mask);
+        }
+    }
+
+    pub fn stencil_op(&self, sfail: GLenum, dpfail: GLenum, dppass: GLenum) {
+        unsafe {
+            self.fns.glStencilOp(sfail, dpfail, dppass);
+        }
+    }
+
+    pub fn stencil_op_separate(&self, face: GLenum, sfail: GLenum, dpfail: GLenum, dppass: GLenum) {
+        unsafe {
+            self.fns.glStencilOpSeparate(face, sfail, dpfail, dppass);
+        }
+    }
+
+    /// Specifies a level of the texture bound to `target`. `opt_data` must hold the whole image
+    /// as laid out by the current `UNPACK_ALIGNMENT` and `UNPACK_ROW_LENGTH`.
+    pub fn tex_image_2d(&self,
+                        target: GLenum,
+                        level: GLint,
+                        internal_format: GLint,
+                        width: GLsizei,
+                        height: GLsizei,
+                        border: GLint,
+                        format: GLenum,
+                        ty: GLenum,
+                        opt_data: Option<&[u8]>)
+                        -> Result<(), PixelError> {
+        match opt_data {
+            Some(data) => {
+                try!(self.check_unpack_size(data, width, height, format, ty));
+                unsafe {
+                    let pdata = mem::transmute(data.as_ptr());
+                    self.fns.glTexImage2D(target, level, internal_format, width, height, border, format, ty,
+                                     pdata);
+                }
+            }
+            None => {
+                unsafe {
+                    self.fns.glTexImage2D(target, level, internal_format, width, height, border, format, ty,
+                                     ptr::null());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces a region of a level of the texture bound to `target`. `opt_data` must hold the
+    /// whole region as laid out by the current `UNPACK_ALIGNMENT` and `UNPACK_ROW_LENGTH`.
+    pub fn tex_sub_image_2d(&self,
+                            target: GLenum,
+                            level: GLint,
+                            xoffset: GLint,
+                            yoffset: GLint,
+                            width: GLsizei,
+                            height: GLsizei,
+                            format: GLenum,
+                            ty: GLenum,
+                            opt_data: Option<&[u8]>)
+                            -> Result<(), PixelError> {
+        match opt_data {
+            Some(data) => {
+                try!(self.check_unpack_size(data, width, height, format, ty));
+                unsafe {
+                    let pdata = mem::transmute(data.as_ptr());
+                    self.fns.glTexSubImage2D(target, level, xoffset, yoffset, width, height, format, ty,
+                                       pdata);
+                }
+            }
+            None => {
+                unsafe {
+                    self.fns.glTexSubImage2D(target, level, xoffset, yoffset, width, height, format, ty,
+                                       ptr::null());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn tex_parameter_f(&self, target: GLenum, pname: GLenum, param: GLfloat) {
+        unsafe {
+            self.fns.glTexParameterf(target, pname, param);
+        }
+    }
+
+    /// Sets the texture parameter `pname`. `params` must hold every value of the parameter.
+    pub fn tex_parameter_fv(&self, target: GLenum, pname: GLenum, params: &[GLfloat])
+                            -> Result<(), ParameterError> {
+        try!(check_tex_parameter_count(pname, params.len()));
+        unsafe {
+            self.fns.glTexParameterfv(target, pname, params.as_ptr() as *mut GLfloat);
+        }
+        Ok(())
+    }
+
+    // Checks that `data` holds a whole image for the current unpack alignment and row length.
+    fn check_unpack_size(&self, data: &[u8], width: GLsizei, height: GLsizei, format: GLenum, ty: GLenum)
+                         -> Result<(), PixelError> {
+        let required = try!(self.pixel_store().unpack_size(width, height, format, ty));
+        pixels::check_buffer_size(data.len(), required)
+    }
+
+    pub fn tex_parameter_i(&self, target: GLenum, pname: GLenum, param: GLint) {
+        unsafe {
+            self.fns.glTexParameteri(target, pname, param);
+        }
+    }
+
+    /// Sets the texture parameter `pname`. `params` must hold every value of the parameter.
+    pub fn tex_parameter_iv(&self, target: GLenum, pname: GLenum, params: &[GLint])
+                            -> Result<(), ParameterError> {
+        try!(check_tex_parameter_count(pname, params.len()));
+        unsafe {
+            self.fns.glTexParameteriv(target, pname, params.as_ptr() as *mut GLint);
+        }
+        Ok(())
+    }
+
+    pub fn uniform_1f(&self, location: GLint, x: GLfloat) {
+        unsafe {
+            self.fns.glUniform1f(location, x);
+        }
+    }
+
+    pub fn uniform_1fv(&self, location: GLint, values: &[f32]) -> Result<(), UniformLengthError> {
+        let count = try!(uniform_count(values.len(), 1));
+        unsafe {
+            self.fns.glUniform1fv(location, count, values.as_ptr() as *mut GLfloat);
+        }
+        Ok(())
+    }
+
+    pub fn uniform_1i(&self, location: GLint, x: GLint) {
+        unsafe {
+            self.fns.glUniform1i(location, x);
+        }
+    }
+
+    pub fn uniform_1iv(&self, location: GLint, values: &[i32]) -> Result<(), UniformLengthError> {
+        let count = try!(uniform_count(values.len(), 1));
+        unsafe {
+            self.fns.glUniform1iv(location, count, values.as_ptr() as *mut GLint);
+        }
+        Ok(())
+    }
+
+    pub fn uniform_2f(&self, location: GLint, x: GLfloat, y: GLfloat) {
+        unsafe {
+            self.fns.glUniform2f(location, x, y);
+        }
+    }
+
+    pub fn uniform_2fv(&self, location: GLint, values: &[f32]) -> Result<(), UniformLengthError> {
+        let count = try!(uniform_count(values.len(), 2));
+        unsafe {
+            self.fns.glUniform2fv(location, count, values.as_ptr() as *mut GLfloat);
+        }
+        Ok(())
+    }
+
+    pub fn uniform_2i(&self, location: GLint, x: GLint, y: GLint) {
+        unsafe {
+            self.fns.glUniform2i(location, x, y);
+        }
+    }
+
+    pub fn uniform_2iv(&self, location: GLint, values: &[i32]) -> Result<(), UniformLengthError> {
+        let count = try!(uniform_count(values.len(), 2));
+        unsafe {
+            self.fns.glUniform2iv(location, count, values.as_ptr() as *mut GLint);
+        }
+        Ok(())
+    }
+
+    pub fn uniform_3f(&self, location: GLint, x: GLfloat, y: GLfloat, z: GLfloat) {
+        unsafe {
+            self.fns.glUniform3f(location, x, y, z);
+        }
+    }
+
+    pub fn uniform_3fv(&self, location: GLint, values: &[f32]) -> Result<(), UniformLengthError> {
+        let count = try!(uniform_count(values.len(), 3));
+        unsafe {
+            self.fns.glUniform3fv(location, count, values.as_ptr() as *mut GLfloat);
+        }
+        Ok(())
+    }
+
+    pub fn uniform_3i(&self, location: GLint, x: GLint, y: GLint, z: GLint) {
+        unsafe {
+            self.fns.glUniform3i(location, x, y, z);
+        }
+    }
+
+    pub fn uniform_3iv(&self, location: GLint, values: &[i32]) -> Result<(), UniformLengthError> {
+        let count = try!(uniform_count(values.len(), 3));
+        unsafe {
+            self.fns.glUniform3iv(location, count, values.as_ptr() as *mut GLint);
+        }
+        Ok(())
+    }
+
+    pub fn uniform_4f(&self, location: GLint, x: GLfloat, y: GLfloat, z: GLfloat, w: GLfloat) {
+        unsafe {
+            self.fns.glUniform4f(location, x, y, z, w);
+        }
+    }
+
+    pub fn uniform_4fv(&self, location: GLint, values: &[f32]) -> Result<(), UniformLengthError> {
+        let count = try!(uniform_count(values.len(), 4));
+        unsafe {
+            self.fns.glUniform4fv(location, count, values.as_ptr() as *mut GLfloat);
+        }
+        Ok(())
+    }
+
+    pub fn uniform_4i(&self, location: GLint, x: GLint, y: GLint, z: GLint, w: GLint) {
+        unsafe {
+            self.fns.glUniform4i(location, x, y, z, w);
+        }
+    }
+
+    pub fn uniform_4iv(&self, location: GLint, values: &[i32]) -> Result<(), UniformLengthError> {
+        let count = try!(uniform_count(values.len(), 4));
+        unsafe {
+            self.fns.glUniform4iv(location, count, values.as_ptr() as *mut GLint);
+        }
+        Ok(())
+    }
+
+    pub fn uniform_matrix_2fv(&self, location: GLint, transpose: bool, value: &[f32])
+                              -> Result<(), UniformLengthError> {
+        let count = try!(uniform_count(value.len(), 4));
+        unsafe {
+            self.fns.glUniformMatrix2fv(location, count, transpose as GLboolean,
+                                        value.as_ptr() as *mut GLfloat);
+        }
+        Ok(())
+    }
+
+    pub fn uniform_matrix_3fv(&self, location: GLint, transpose: bool, value: &[f32])
+                              -> Result<(), UniformLengthError> {
+        let count = try!(uniform_count(value.len(), 9));
+        unsafe {
+            self.fns.glUniformMatrix3fv(location, count, transpose as GLboolean,
+                                        value.as_ptr() as *mut GLfloat);
+        }
+        Ok(())
+    }
+
+    pub fn uniform_matrix_4fv(&self, location: GLint, transpose: bool, value: &[f32])
+                              -> Result<(), UniformLengthError> {
+        let count = try!(uniform_count(value.len(), 16));
+        unsafe {
+            self.fns.glUniformMatrix4fv(location, count, transpose as GLboolean,
+                                        value.as_ptr() as *mut GLfloat);
+        }
+        Ok(())
+    }
+
+    pub fn use_program(&self, program: GLuint) {
+        unsafe {
+            self.fns.glUseProgram(program);
+        }
+    }
+
+    pub fn validate_program(&self, program: GLuint) {
+        unsafe {
+            self.fns.glValidateProgram(program);
+        }
+    }
+
+    pub fn vertex_attrib_1f(&self, index: GLuint, x: GLfloat) {
+        unsafe {
+            self.fns.glVertexAttrib1f(index, x);
+        }
+    }
+
+    pub fn vertex_attrib_1fv(&self, index: GLuint, values: &[GLfloat, ..1]) {
+        unsafe {
+            self.fns.glVertexAttrib1fv(index, values.as_ptr() as *mut GLfloat);
+        }
+    }
+
+    pub fn vertex_attrib_2f(&self, index: GLuint, x: GLfloat, y: GLfloat) {
+        unsafe {
+            self.fns.glVertexAttrib2f(index, x, y);
+        }
+    }
+
+    pub fn vertex_attrib_2fv(&self, index: GLuint, values: &[GLfloat, ..2]) {
+        unsafe {
+            self.fns.glVertexAttrib2fv(index, values.as_ptr() as *mut GLfloat);
+        }
+    }
+
+    pub fn vertex_attrib_3f(&self, index: GLuint, x: GLfloat, y: GLfloat, z: GLfloat) {
+        unsafe {
+            self.fns.glVertexAttrib3f(index, x, y, z);
+        }
+    }
+
+    pub fn vertex_attrib_3fv(&self, index: GLuint, values: &[GLfloat, ..3]) {
+        unsafe {
+            self.fns.glVertexAttrib3fv(index, values.as_ptr() as *mut GLfloat);
+        }
+    }
+
+    pub fn vertex_attrib_4f(&self, index: GLuint, x: GLfloat, y: GLfloat, z: GLfloat, w: GLfloat) {
+        unsafe {
+            self.fns.glVertexAttrib4f(index, x, y, z, w);
+        }
+    }
+
+    pub fn vertex_attrib_4fv(&self, index: GLuint, values: &[GLfloat, ..4]) {
+        unsafe {
+            self.fns.glVertexAttrib4fv(index, values.as_ptr() as *mut GLfloat);
+        }
+    }
+
+    /// Points attribute `index` at `size` components of type `T` per vertex, starting `offset`
+    /// bytes into the bound `ARRAY_BUFFER`. Returns an error without calling GL if `size` is not
+    /// 1 to 4 or `stride` is negative.
+    pub fn vertex_attrib_pointer<T: AttribComponent>(&self,
+                                                     index: GLuint,
+                                                     size: GLint,
+                                                     normalized: bool,
+                                                     stride: GLsizei,
+                                                     offset: uint)
+                                                     -> Result<(), VertexAttribError> {
+        if size < 1 || size > 4 {
+            return Err(VertexAttribError::InvalidSize(size))
+        }
+        if stride < 0 {
+            return Err(VertexAttribError::NegativeStride(stride))
+        }
+        let ty = AttribComponent::gl_type(None::<T>);
+        unsafe {
+            self.fns.glVertexAttribPointer(index,
+                                           size,
+                                           ty,
+                                           normalized as GLboolean,
+                                           stride,
+                                           offset as *const GLvoid);
+        }
+        Ok(())
+    }
+
+    #[deprecated = "use vertex_attrib_pointer::<f32>"]
+    pub fn vertex_attrib_pointer_f32(&self,
+                                     index: GLuint,
+                                     size: GLint,
+                                     normalized: bool,
+                                     stride: GLsizei,
+                                     offset: GLuint) {
+        unsafe {
+            self.fns.glVertexAttribPointer(index,
+                                           size,
+                                           FLOAT,
+                                           normalized as GLboolean,
+                                           stride,
+                                           offset as uint as *const GLvoid);
+        }
+    }
+
+    #[deprecated = "use vertex_attrib_pointer::<i8>"]
+    pub fn vertex_attrib_pointer_i8(&self,
+                                    index: GLuint,
+                                    size: GLint,
+                                    normalized: bool,
+                                    stride: GLsizei,
+                                    offset: GLuint) {
+        unsafe {
+            self.fns.glVertexAttribPointer(index,
+                                           size,
+                                           BYTE,
+                                           normalized as GLboolean,
+                                           stride,
+                                           offset as uint as *const GLvoid);
+        }
+    }
+
+    #[deprecated = "use vertex_attrib_pointer::<i32>"]
+    pub fn vertex_attrib_pointer_i32(&self,
+                                     index: GLuint,
+                                     size: GLint,
+                                     normalized: bool,
+                                     stride: GLsizei,
+                                     offset: GLuint) {
+        unsafe {
+            self.fns.glVertexAttribPointer(index,
+                                           size,
+                                           INT,
+                                           normalized as GLboolean,
+                                           stride,
+                                           offset as uint as *const GLvoid);
+        }
+    }
+
+    #[deprecated = "use vertex_attrib_pointer::<u8>"]
+    pub fn vertex_attrib_pointer_u8(&self,
+                                    index: GLuint,
+                                    size: GLint,
+                                    normalized: bool,
+                                    stride: GLsizei,
+                                    offset: GLuint) {
+        unsafe {
+            self.fns.glVertexAttribPointer(index,
+                                           size,
+                                           UNSIGNED_BYTE,
+                                           normalized as GLboolean,
+                                           stride,
+                                           offset as uint as *const GLvoid);
+        }
+    }
+
+    #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+    pub fn vertex_attrib_divisor(&self, index: GLuint, divisor: GLuint) {
+        unsafe {
+            self.fns.glVertexAttribDivisor(index, divisor);
+        }
+    }
+
+    pub fn viewport(&self, x: GLint, y: GLint, width: GLsizei, height: GLsizei) {
+        unsafe {
+            self.fns.glViewport(x, y, width, height);
+        }
+    }
+
+    #[cfg(target_os="android")]
+    pub fn egl_image_target_texture2d_oes(&self, target: GLenum, image: GLeglImageOES) {
+        unsafe {
+            return self.fns.glEGLImageTargetTexture2DOES(target, image);
+        }
+
+    }
+
+    #[cfg(target_os="android")]
+    pub fn egl_image_target_renderbuffer_storage_oes(&self, target: GLenum, image: GLeglImageOES) {
+        unsafe {
+            return self.fns.glEGLImageTargetRenderbufferStorageOES(target, image);
+        }
+    }
+}
+
+// The same API operating on the global context loaded by `load_with`
+
+pub fn active_texture(texture: GLenum) {
+    global().active_texture(texture);
+}
+
+pub fn attach_shader(program: GLuint, shader: GLuint) {
+    global().attach_shader(program, shader);
+}
+
+pub fn bind_attrib_location(program: GLuint, index: GLuint, name: &str) {
+    global().bind_attrib_location(program, index, name);
+}
+
+pub fn bind_buffer(target: GLenum, buffer: GLuint) {
+    global().bind_buffer(target, buffer);
+}
+
+pub fn bind_framebuffer(target: GLenum, framebuffer: GLuint) {
+    global().bind_framebuffer(target, framebuffer);
+}
+
+pub fn bind_renderbuffer(target: GLenum, renderbuffer: GLuint) {
+    global().bind_renderbuffer(target, renderbuffer);
+}
+
+pub fn bind_texture(target: GLenum, texture: GLuint) {
+    global().bind_texture(target, texture);
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn bind_vertex_array(array: GLuint) {
+    global().bind_vertex_array(array);
+}
+
+pub fn blend_color(red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf) {
+    global().blend_color(red, green, blue, alpha);
+}
+
+pub fn blend_equation(mode: GLenum) {
+    global().blend_equation(mode);
+}
+
+pub fn blend_equation_separate(mode_rgb: GLenum, mode_alpha: GLenum) {
+    global().blend_equation_separate(mode_rgb, mode_alpha);
+}
+
+pub fn blend_func(sfactor: GLenum, dfactor: GLenum) {
+    global().blend_func(sfactor, dfactor);
+}
+
+pub fn blend_func_separate(src_rgb: GLenum, dst_rgb: GLenum, src_alpha: GLenum, dst_alpha: GLenum) {
+    global().blend_func_separate(src_rgb, dst_rgb, src_alpha, dst_alpha);
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn buffer_data<T>(target: GLenum, data: &[T], usage: GLenum) {
+    global().buffer_data(target, data, usage);
+}
+
+#[cfg(target_os="android")]
+pub fn buffer_data(target: GLenum, data: &[f32], usage: GLenum) {
+    global().buffer_data(target, data, usage);
+}
+
+pub fn buffer_sub_data<T>(target: GLenum, element_offset_index: uint, data: &[T]) {
+    global().buffer_sub_data(target, element_offset_index, data);
+}
+
+pub fn check_framebuffer_status(target: GLenum) -> GLenum {
+    global().check_framebuffer_status(target)
+}
+
+pub fn clear(mask: GLbitfield) {
+    global().clear(mask);
+}
+
+pub fn clear_color(red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf) {
+    global().clear_color(red, green, blue, alpha);
+}
+
+pub fn clear_stencil(s: GLint) {
+    global().clear_stencil(s);
+}
+
+pub fn compile_shader(shader: GLuint) {
+    global().compile_shader(shader);
+}
+
+pub fn compressed_tex_image_2d(target: GLenum,
+                               level: GLint,
+                               internal_format: GLenum,
+                               width: GLsizei,
+                               height: GLsizei,
+                               border: GLint,
+                               data: &[u8]) -> Result<(), PixelError> {
+    global().compressed_tex_image_2d(target, level, internal_format, width, height, border, data)
+}
+
+pub fn compressed_tex_sub_image_2d(target: GLenum,
+                                   level: GLint,
+                                   xoffset: GLint,
+                                   yoffset: GLint,
+                                   width: GLsizei,
+                                   height: GLsizei,
+                                   format: GLenum,
+                                   data: &[u8]) -> Result<(), PixelError> {
+    global().compressed_tex_sub_image_2d(target, level, xoffset, yoffset, width, height, format, data)
+}
+
+pub fn copy_tex_image_2d(target: GLenum,
+                         level: GLint,
+                         internal_format: GLenum,
+                         x: GLint,
+                         y: GLint,
+                         width: GLsizei,
+                         height: GLsizei,
+                         border: GLint) {
+    global().copy_tex_image_2d(target, level, internal_format, x, y, width, height, border);
+}
+
+pub fn copy_tex_sub_image_2d(target: GLenum,
+                             level: GLint,
+                             xoffset: GLint,
+                             yoffset: GLint,
+                             x: GLint,
+                             y: GLint,
+                             width: GLsizei,
+                             height: GLsizei) {
+    global().copy_tex_sub_image_2d(target, level, xoffset, yoffset, x, y, width, height);
+}
+
+pub fn create_program() -> GLuint {
+    global().create_program()
+}
+
+pub fn create_shader(shader_type: GLenum) -> GLuint {
+    global().create_shader(shader_type)
+}
+
+pub fn cull_face(mode: GLenum) {
+    global().cull_face(mode);
+}
+
+pub fn delete_buffers(buffers: &[GLuint]) {
+    global().delete_buffers(buffers);
+}
+
+pub fn delete_frame_buffers(frame_buffers: &[GLuint]) {
+    global().delete_frame_buffers(frame_buffers);
+}
+
+pub fn delete_program(program: GLuint) {
+    global().delete_program(program);
+}
+
+pub fn delete_render_buffers(render_buffers: &[GLuint]) {
+    global().delete_render_buffers(render_buffers);
+}
+
+pub fn delete_shader(shader: GLuint) {
+    global().delete_shader(shader);
+}
+
+pub fn delete_textures(textures: &[GLuint]) {
+    global().delete_textures(textures);
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn delete_vertex_arrays(arrays: &[GLuint]) {
+    global().delete_vertex_arrays(arrays);
+}
+
+pub fn depth_func(func: GLenum) {
+    global().depth_func(func);
+}
+
+pub fn depth_mask(flag: bool) {
+    global().depth_mask(flag);
+}
+
+pub fn detach_shader(program: GLuint, shader: GLuint) {
+    global().detach_shader(program, shader);
+}
+
+pub fn draw_arrays(mode: GLenum, first: GLint, count: GLsizei) {
+    global().draw_arrays(mode, first, count);
+}
+
+pub fn draw_elements(mode: GLenum, count: GLsizei, element_type: GLenum, indices: Option<&[u8]>) {
+    global().draw_elements(mode, count, element_type, indices);
+}
+
+pub fn draw_elements_indexed<T: IndexType>(mode: GLenum, count: GLsizei, indices: &[T])
+                                           -> Result<(), IndexCountError> {
+    global().draw_elements_indexed(mode, count, indices)
+}
+
+pub fn draw_elements_offset<T: IndexType>(mode: GLenum, count: GLsizei, offset: uint)
+                                          -> Result<(), IndexCountError> {
+    global().draw_elements_offset::<T>(mode, count, offset)
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn draw_arrays_instanced(mode: GLenum, first: GLint, count: GLsizei, primcount: GLsizei) {
+    global().draw_arrays_instanced(mode, first, count, primcount);
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn draw_elements_instanced(mode: GLenum, count: GLsizei, element_type: GLenum, indices: Option<&[u8]>, primcount: GLsizei) {
+    global().draw_elements_instanced(mode, count, element_type, indices, primcount);
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn draw_elements_instanced_indexed<T: IndexType>(mode: GLenum,
+                                                     count: GLsizei,
+                                                     indices: &[T],
+                                                     primcount: GLsizei)
+                                                     -> Result<(), IndexCountError> {
+    global().draw_elements_instanced_indexed(mode, count, indices, primcount)
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn draw_elements_instanced_offset<T: IndexType>(mode: GLenum,
+                                                    count: GLsizei,
+                                                    offset: uint,
+                                                    primcount: GLsizei)
+                                                    -> Result<(), IndexCountError> {
+    global().draw_elements_instanced_offset::<T>(mode, count, offset, primcount)
+}
+
+pub fn enable(cap: GLenum) {
+    global().enable(cap);
+}
+
+pub fn disable(cap: GLenum) {
+    global().disable(cap);
+}
+
+pub fn enable_vertex_attrib_array(index: GLuint) {
+    global().enable_vertex_attrib_array(index);
+}
+
+pub fn disable_vertex_attrib_array(index: GLuint) {
+    global().disable_vertex_attrib_array(index);
+}
+
+pub fn finish() {
+    global().finish();
+}
+
+pub fn flush() {
+    global().flush();
+}
+
+pub fn framebuffer_renderbuffer(target: GLenum,
+                                attachment: GLenum,
+                                renderbuffertarget: GLenum,
+                                renderbuffer: GLuint) {
+    global().framebuffer_renderbuffer(target, attachment, renderbuffertarget, renderbuffer);
+}
+
+pub fn framebuffer_texture_2d(target: GLenum,
+                              attachment: GLenum,
+                              textarget: GLenum,
+                              texture: GLuint,
+                              level: GLint) {
+    global().framebuffer_texture_2d(target, attachment, textarget, texture, level);
+}
+
+pub fn front_face(mode: GLenum) {
+    global().front_face(mode);
+}
+
+pub fn gen_buffers(n: GLsizei) -> Vec<GLuint> {
+    global().gen_buffers(n)
+}
+
+pub fn gen_framebuffers(n: GLsizei) -> Vec<GLuint> {
+    global().gen_framebuffers(n)
+}
+
+pub fn gen_renderbuffers(n: GLsizei) -> Vec<GLuint> {
+    global().gen_renderbuffers(n)
+}
+
+pub fn gen_textures(n: GLsizei) -> Vec<GLuint> {
+    global().gen_textures(n)
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn gen_vertex_arrays(n: GLsizei) -> Vec<GLuint> {
+    global().gen_vertex_arrays(n)
+}
+
+pub fn generate_mipmap(target: GLenum) {
+    global().generate_mipmap(target);
+}
+
+pub fn get_active_attrib(program: GLuint, index: GLuint) -> ActiveInfo {
+    global().get_active_attrib(program, index)
+}
+
+pub fn get_active_uniform(program: GLuint, index: GLuint) -> ActiveInfo {
+    global().get_active_uniform(program, index)
+}
+
+pub fn get_attached_shaders(program: GLuint) -> Vec<GLuint> {
+    global().get_attached_shaders(program)
+}
+
+pub fn get_attrib_location(program: GLuint, name: &str) -> c_int {
+    global().get_attrib_location(program, name)
+}
+
+pub fn get_compressed_texture_formats() -> Vec<GLenum> {
+    global().get_compressed_texture_formats()
+}
+
+pub fn get_error() -> GLenum {
+    global().get_error()
+}
+
+pub fn get_framebuffer_attachment_parameter_iv(target: GLenum,
+                                               attachment: GLenum,
+                                               pname: GLenum) -> GLint {
+    global().get_framebuffer_attachment_parameter_iv(target, attachment, pname)
+}
+
+pub fn get_integer_v(pname: GLenum, result: &mut [GLint]) {
+    global().get_integer_v(pname, result);
+}
+
+pub fn get_program_info_log(program: GLuint) -> String {
+    global().get_program_info_log(program)
+}
+
+pub fn get_program_iv(program: GLuint, pname: GLenum) -> GLint {
+    global().get_program_iv(program, pname)
+}
+
+pub fn get_renderbuffer_parameter_iv(target: GLenum, pname: GLenum) -> GLint {
+    global().get_renderbuffer_parameter_iv(target, pname)
+}
+
+pub fn get_shader_info_log(shader: GLuint) -> String {
+    global().get_shader_info_log(shader)
+}
+
+pub fn get_string(which: GLenum) -> String {
+    global().get_string(which)
+}
+
+pub fn get_tex_parameter_fv(target: GLenum, pname: GLenum) -> GLfloat {
+    global().get_tex_parameter_fv(target, pname)
+}
+
+pub fn get_tex_parameter_iv(target: GLenum, pname: GLenum) -> GLint {
+    global().get_tex_parameter_iv(target, pname)
+}
+
+pub fn get_shader_iv(shader: GLuint, pname: GLenum) -> GLint {
+    global().get_shader_iv(shader, pname)
+}
+
+pub fn get_uniform_fv(program: GLuint, location: GLint, result: &mut [GLfloat]) {
+    global().get_uniform_fv(program, location, result);
+}
+
+pub fn get_uniform_iv(program: GLuint, location: GLint, result: &mut [GLint]) {
+    global().get_uniform_iv(program, location, result);
+}
+
+pub fn get_uniform_location(program: GLuint, name: &str) -> c_int {
+    global().get_uniform_location(program, name)
+}
+
+pub fn get_vertex_attrib_fv(index: GLuint, pname: GLenum, result: &mut [GLfloat]) {
+    global().get_vertex_attrib_fv(index, pname, result);
+}
+
+pub fn get_vertex_attrib_iv(index: GLuint, pname: GLenum, result: &mut [GLint]) {
+    global().get_vertex_attrib_iv(index, pname, result);
+}
+
+pub fn get_vertex_attrib_pointer_v(index: GLuint, pname: GLenum) -> uint {
+    global().get_vertex_attrib_pointer_v(index, pname)
+}
+
+pub fn get_vertex_attrib_state(index: GLuint) -> VertexAttribState {
+    global().get_vertex_attrib_state(index)
+}
+
+pub fn hint(target: GLenum, mode: GLenum) {
+    global().hint(target, mode);
+}
+
+pub fn is_buffer(buffer: GLuint) -> bool {
+    global().is_buffer(buffer)
+}
+
+pub fn is_enabled(cap: GLenum) -> bool {
+    global().is_enabled(cap)
+}
+
+pub fn is_framebuffer(framebuffer: GLuint) -> bool {
+    global().is_framebuffer(framebuffer)
+}
+
+pub fn is_program(program: GLuint) -> bool {
+    global().is_program(program)
+}
+
+pub fn is_renderbuffer(renderbuffer: GLuint) -> bool {
+    global().is_renderbuffer(renderbuffer)
+}
 
 pub fn is_shader(shader: GLuint) -> bool {
-  unsafe {
-    glIsShader(shader) > 0
-  }
+    global().is_shader(shader)
 }
 
 pub fn is_texture(texture: GLuint) -> bool {
-  unsafe {
-    glIsTexture(texture) > 0
-  }
+    global().is_texture(texture)
 }
 
 pub fn line_width(width: GLfloat) {
-  unsafe {
-    glLineWidth(width);
-  }
+    global().line_width(width);
 }
 
 pub fn link_program(program: GLuint) {
-    unsafe {
-        return glLinkProgram(program);
-    }
+    global().link_program(program);
 }
 
 pub fn pixel_store_i(pname: GLenum, param: GLint) {
-    unsafe {
-        glPixelStorei(pname, param);
-    }
+    global().pixel_store_i(pname, param);
+}
+
+pub fn pixel_store() -> PixelStore {
+    global().pixel_store()
+}
+
+pub fn refresh_pixel_store() -> PixelStore {
+    global().refresh_pixel_store()
+}
+
+pub fn set_pixel_store(store: &PixelStore) {
+    global().set_pixel_store(store);
+}
+
+pub fn with_pixel_store<R>(store: &PixelStore, f: || -> R) -> R {
+    global().with_pixel_store(store, f)
 }
 
 #[cfg(not(target_os = "android"))]
 pub fn polygon_mode(face: GLenum, mode: GLenum) {
-    unsafe {
-        glPolygonMode(face, mode);
-    }
+    global().polygon_mode(face, mode);
 }
 
-pub fn read_pixels(x: GLint, y: GLint, width: GLsizei, height: GLsizei, format: GLenum, pixel_type: GLenum) -> Vec<u8> {
-    let colors = match format {
-        RGB => 3,
-        RGBA => 3,
-        _ => panic!("unsupported format for read_pixels"),
-    };
-    let depth = match pixel_type {
-        UNSIGNED_BYTE => 1,
-        _ => panic!("unsupported pixel_type for read_pixels"),
-    };
-
-    let len = (width * height * colors * depth) as uint;
-    let mut pixels: Vec<u8> = Vec::new();
-    pixels.reserve(len);
+pub fn read_pixels(x: GLint, y: GLint, width: GLsizei, height: GLsizei, format: GLenum, pixel_type: GLenum)
+                   -> Result<Vec<u8>, PixelError> {
+    global().read_pixels(x, y, width, height, format, pixel_type)
+}
 
-    unsafe {
-        // We don't want any alignment padding on pixel rows.
-        glPixelStorei(PACK_ALIGNMENT, 1);
-        glReadPixels(x, y, width, height, format, pixel_type, pixels.as_mut_ptr() as *mut c_void);
-        pixels.set_len(len);
-    }
+pub fn read_pixels_into(x: GLint,
+                        y: GLint,
+                        width: GLsizei,
+                        height: GLsizei,
+                        format: GLenum,
+                        pixel_type: GLenum,
+                        pixels: &mut [u8])
+                        -> Result<(), PixelError> {
+    global().read_pixels_into(x, y, width, height, format, pixel_type, pixels)
+}
 
-    pixels
+pub fn renderbuffer_storage(target: GLenum,
+                            internalformat: GLenum,
+                            width: GLsizei,
+                            height: GLsizei) {
+    global().renderbuffer_storage(target, internalformat, width, height);
 }
 
 pub fn scissor(x: GLint, y: GLint, width: GLsizei, height: GLsizei) {
-    unsafe {
-        glScissor(x, y, width, height);
-    }
+    global().scissor(x, y, width, height);
 }
 
 pub fn shader_source(shader: GLuint, strings: &[&[u8]]) {
-    let pointers: Vec<*const u8> = strings.iter().map(|string| (*string).as_ptr()).collect();
-    let lengths: Vec<GLint> = strings.iter().map(|string| string.len() as GLint).collect();
-    unsafe {
-        glShaderSource(shader, pointers.len() as GLsizei,
-                       pointers.as_ptr() as *const *const GLchar, lengths.as_ptr());
-    }
-    drop(lengths);
-    drop(pointers);
+    global().shader_source(shader, strings);
+}
+
+pub fn stencil_func(func: GLenum, reference: GLint, mask: GLuint) {
+    global().stencil_func(func, reference, mask);
+}
+
+pub fn stencil_func_separate(face: GLenum, func: GLenum, reference: GLint, mask: GLuint) {
+    global().stencil_func_separate(face, func, reference, mask);
+}
+
+pub fn stencil_mask(mask: GLuint) {
+    global().stencil_mask(mask);
+}
+
+pub fn stencil_mask_separate(face: GLenum, mask: GLuint) {
+    global().stencil_mask_separate(face, mask);
+}
+
+pub fn stencil_op(sfail: GLenum, dpfail: GLenum, dppass: GLenum) {
+    global().stencil_op(sfail, dpfail, dppass);
+}
+
+pub fn stencil_op_separate(face: GLenum, sfail: GLenum, dpfail: GLenum, dppass: GLenum) {
+    global().stencil_op_separate(face, sfail, dpfail, dppass);
 }
 
-// FIXME: Does not verify buffer size -- unsafe!
 pub fn tex_image_2d(target: GLenum,
                     level: GLint,
                     internal_format: GLint,
@@ -893,25 +2797,11 @@ pub fn tex_image_2d(target: GLenum,
                     border: GLint,
                     format: GLenum,
                     ty: GLenum,
-                    opt_data: Option<&[u8]>) {
-    match opt_data {
-        Some(data) => {
-            unsafe {
-                let pdata = mem::transmute(data.as_ptr());
-                glTexImage2D(target, level, internal_format, width, height, border, format, ty,
-                                 pdata);
-            }
-        }
-        None => {
-            unsafe {
-                glTexImage2D(target, level, internal_format, width, height, border, format, ty,
-                                 ptr::null());
-            }
-        }
-    }
+                    opt_data: Option<&[u8]>)
+                    -> Result<(), PixelError> {
+    global().tex_image_2d(target, level, internal_format, width, height, border, format, ty, opt_data)
 }
 
-// FIXME: Does not verify buffer size -- unsafe!
 pub fn tex_sub_image_2d(target: GLenum,
                         level: GLint,
                         xoffset: GLint,
@@ -920,168 +2810,214 @@ pub fn tex_sub_image_2d(target: GLenum,
                         height: GLsizei,
                         format: GLenum,
                         ty: GLenum,
-                        opt_data: Option<&[u8]>) {
-    match opt_data {
-        Some(data) => {
-            unsafe {
-                let pdata = mem::transmute(data.as_ptr());
-                glTexSubImage2D(target, level, xoffset, yoffset, width, height, format, ty,
-                                   pdata);
-            }
-        }
-        None => {
-            unsafe {
-                glTexSubImage2D(target, level, xoffset, yoffset, width, height, format, ty,
-                                   ptr::null());
-            }
-        }
-    }
+                        opt_data: Option<&[u8]>)
+                        -> Result<(), PixelError> {
+    global().tex_sub_image_2d(target, level, xoffset, yoffset, width, height, format, ty, opt_data)
+}
+
+pub fn tex_parameter_f(target: GLenum, pname: GLenum, param: GLfloat) {
+    global().tex_parameter_f(target, pname, param);
+}
+
+pub fn tex_parameter_fv(target: GLenum, pname: GLenum, params: &[GLfloat])
+                        -> Result<(), ParameterError> {
+    global().tex_parameter_fv(target, pname, params)
 }
 
 pub fn tex_parameter_i(target: GLenum, pname: GLenum, param: GLint) {
-    unsafe {
-        glTexParameteri(target, pname, param);
-    }
+    global().tex_parameter_i(target, pname, param);
+}
+
+pub fn tex_parameter_iv(target: GLenum, pname: GLenum, params: &[GLint])
+                        -> Result<(), ParameterError> {
+    global().tex_parameter_iv(target, pname, params)
 }
 
 pub fn uniform_1f(location: GLint, x: GLfloat) {
-    unsafe {
-        glUniform1f(location, x);
-    }
+    global().uniform_1f(location, x);
+}
+
+pub fn uniform_1fv(location: GLint, values: &[f32]) -> Result<(), UniformLengthError> {
+    global().uniform_1fv(location, values)
 }
 
 pub fn uniform_1i(location: GLint, x: GLint) {
-    unsafe {
-        glUniform1i(location, x);
-    }
+    global().uniform_1i(location, x);
+}
+
+pub fn uniform_1iv(location: GLint, values: &[i32]) -> Result<(), UniformLengthError> {
+    global().uniform_1iv(location, values)
 }
 
 pub fn uniform_2f(location: GLint, x: GLfloat, y: GLfloat) {
-    unsafe {
-        glUniform2f(location, x, y);
-    }
+    global().uniform_2f(location, x, y);
+}
+
+pub fn uniform_2fv(location: GLint, values: &[f32]) -> Result<(), UniformLengthError> {
+    global().uniform_2fv(location, values)
+}
+
+pub fn uniform_2i(location: GLint, x: GLint, y: GLint) {
+    global().uniform_2i(location, x, y);
+}
+
+pub fn uniform_2iv(location: GLint, values: &[i32]) -> Result<(), UniformLengthError> {
+    global().uniform_2iv(location, values)
 }
 
 pub fn uniform_3f(location: GLint, x: GLfloat, y: GLfloat, z: GLfloat) {
-    unsafe {
-        glUniform3f(location, x, y, z);
-    }
+    global().uniform_3f(location, x, y, z);
+}
+
+pub fn uniform_3fv(location: GLint, values: &[f32]) -> Result<(), UniformLengthError> {
+    global().uniform_3fv(location, values)
+}
+
+pub fn uniform_3i(location: GLint, x: GLint, y: GLint, z: GLint) {
+    global().uniform_3i(location, x, y, z);
+}
+
+pub fn uniform_3iv(location: GLint, values: &[i32]) -> Result<(), UniformLengthError> {
+    global().uniform_3iv(location, values)
 }
 
 pub fn uniform_4f(location: GLint, x: GLfloat, y: GLfloat, z: GLfloat, w: GLfloat) {
-    unsafe {
-        glUniform4f(location, x, y, z, w);
-    }
+    global().uniform_4f(location, x, y, z, w);
 }
 
-pub fn uniform_matrix_4fv(location: GLint, transpose: bool, value: &[f32]) {
-    unsafe {
-        glUniformMatrix4fv(location,
-                               1 as GLsizei,
-                               transpose as GLboolean,
-                               mem::transmute(&value[0]));
-    }
+pub fn uniform_4fv(location: GLint, values: &[f32]) -> Result<(), UniformLengthError> {
+    global().uniform_4fv(location, values)
+}
+
+pub fn uniform_4i(location: GLint, x: GLint, y: GLint, z: GLint, w: GLint) {
+    global().uniform_4i(location, x, y, z, w);
+}
+
+pub fn uniform_4iv(location: GLint, values: &[i32]) -> Result<(), UniformLengthError> {
+    global().uniform_4iv(location, values)
+}
+
+pub fn uniform_matrix_2fv(location: GLint, transpose: bool, value: &[f32])
+                          -> Result<(), UniformLengthError> {
+    global().uniform_matrix_2fv(location, transpose, value)
+}
+
+pub fn uniform_matrix_3fv(location: GLint, transpose: bool, value: &[f32])
+                          -> Result<(), UniformLengthError> {
+    global().uniform_matrix_3fv(location, transpose, value)
+}
+
+pub fn uniform_matrix_4fv(location: GLint, transpose: bool, value: &[f32])
+                          -> Result<(), UniformLengthError> {
+    global().uniform_matrix_4fv(location, transpose, value)
 }
 
 pub fn use_program(program: GLuint) {
-    unsafe {
-        glUseProgram(program);
-    }
+    global().use_program(program);
 }
 
 pub fn validate_program(program: GLuint) {
-    unsafe {
-        glValidateProgram(program);
-    }
+    global().validate_program(program);
+}
+
+pub fn vertex_attrib_1f(index: GLuint, x: GLfloat) {
+    global().vertex_attrib_1f(index, x);
+}
+
+pub fn vertex_attrib_1fv(index: GLuint, values: &[GLfloat, ..1]) {
+    global().vertex_attrib_1fv(index, values);
+}
+
+pub fn vertex_attrib_2f(index: GLuint, x: GLfloat, y: GLfloat) {
+    global().vertex_attrib_2f(index, x, y);
+}
+
+pub fn vertex_attrib_2fv(index: GLuint, values: &[GLfloat, ..2]) {
+    global().vertex_attrib_2fv(index, values);
+}
+
+pub fn vertex_attrib_3f(index: GLuint, x: GLfloat, y: GLfloat, z: GLfloat) {
+    global().vertex_attrib_3f(index, x, y, z);
+}
+
+pub fn vertex_attrib_3fv(index: GLuint, values: &[GLfloat, ..3]) {
+    global().vertex_attrib_3fv(index, values);
+}
+
+pub fn vertex_attrib_4f(index: GLuint, x: GLfloat, y: GLfloat, z: GLfloat, w: GLfloat) {
+    global().vertex_attrib_4f(index, x, y, z, w);
+}
+
+pub fn vertex_attrib_4fv(index: GLuint, values: &[GLfloat, ..4]) {
+    global().vertex_attrib_4fv(index, values);
+}
+
+pub fn vertex_attrib_pointer<T: AttribComponent>(index: GLuint,
+                                                 size: GLint,
+                                                 normalized: bool,
+                                                 stride: GLsizei,
+                                                 offset: uint)
+                                                 -> Result<(), VertexAttribError> {
+    global().vertex_attrib_pointer::<T>(index, size, normalized, stride, offset)
 }
 
+#[deprecated = "use vertex_attrib_pointer::<f32>"]
+#[allow(deprecated)]
 pub fn vertex_attrib_pointer_f32(index: GLuint,
                                  size: GLint,
                                  normalized: bool,
                                  stride: GLsizei,
                                  offset: GLuint) {
-    unsafe {
-        glVertexAttribPointer(index,
-                                  size,
-                                  FLOAT,
-                                  normalized as GLboolean,
-                                  stride,
-                                  mem::transmute(offset as uint));
-    }
+    global().vertex_attrib_pointer_f32(index, size, normalized, stride, offset);
 }
 
+#[deprecated = "use vertex_attrib_pointer::<i8>"]
+#[allow(deprecated)]
 pub fn vertex_attrib_pointer_i8(index: GLuint,
                                  size: GLint,
                                  normalized: bool,
                                  stride: GLsizei,
                                  offset: GLuint) {
-    unsafe {
-        glVertexAttribPointer(index,
-                                  size,
-                                  BYTE,
-                                  normalized as GLboolean,
-                                  stride,
-                                  mem::transmute(offset as uint));
-    }
+    global().vertex_attrib_pointer_i8(index, size, normalized, stride, offset);
 }
 
+#[deprecated = "use vertex_attrib_pointer::<i32>"]
+#[allow(deprecated)]
 pub fn vertex_attrib_pointer_i32(index: GLuint,
                                  size: GLint,
                                  normalized: bool,
                                  stride: GLsizei,
                                  offset: GLuint) {
-    unsafe {
-        glVertexAttribPointer(index,
-                                  size,
-                                  INT,
-                                  normalized as GLboolean,
-                                  stride,
-                                  mem::transmute(offset as uint));
-    }
+    global().vertex_attrib_pointer_i32(index, size, normalized, stride, offset);
 }
 
+#[deprecated = "use vertex_attrib_pointer::<u8>"]
+#[allow(deprecated)]
 pub fn vertex_attrib_pointer_u8(index: GLuint,
                                  size: GLint,
                                  normalized: bool,
                                  stride: GLsizei,
                                  offset: GLuint) {
-    unsafe {
-        glVertexAttribPointer(index,
-                                  size,
-                                  UNSIGNED_BYTE,
-                                  normalized as GLboolean,
-                                  stride,
-                                  mem::transmute(offset as uint));
-    }
+    global().vertex_attrib_pointer_u8(index, size, normalized, stride, offset);
 }
 
 #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
 pub fn vertex_attrib_divisor(index: GLuint, divisor: GLuint) {
-    unsafe {
-        glVertexAttribDivisor(index, divisor);
-    }
+    global().vertex_attrib_divisor(index, divisor);
 }
 
 pub fn viewport(x: GLint, y: GLint, width: GLsizei, height: GLsizei) {
-    unsafe {
-        glViewport(x, y, width, height);
-    }
+    global().viewport(x, y, width, height);
 }
 
-
 #[cfg(target_os="android")]
 pub fn egl_image_target_texture2d_oes(target: GLenum, image: GLeglImageOES) {
-    unsafe {
-        return glEGLImageTargetTexture2DOES(target, image);
-    }
-
+    global().egl_image_target_texture2d_oes(target, image);
 }
 
 #[cfg(target_os="android")]
 pub fn egl_image_target_renderbuffer_storage_oes(target: GLenum, image: GLeglImageOES) {
-    unsafe {
-        return glEGLImageTargetRenderbufferStorageOES(target, image);
-    }
+    global().egl_image_target_renderbuffer_storage_oes(target, image);
 }
 
 // Apple extensions
@@ -1095,329 +3031,670 @@ pub mod apple {
     }
 }
 
-extern {
+// Entry points
+//
+// Every GL function is called through a table of pointers filled in at runtime, either by
+// `load_with` or, when the `static_link` feature is enabled, from the library linked above.
+
+macro_rules! gl_functions {
+    ($($(#[$attr:meta])* fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)*;)*) => {
+        /// A table of GL entry points.
+        #[allow(non_snake_case)]
+        pub struct FnPtrs {
+            $($name: *const c_void,)*
+        }
+
+        const UNLOADED: FnPtrs = FnPtrs {
+            $($name: 0 as *const c_void,)*
+        };
+
+        #[allow(non_snake_case)]
+        impl FnPtrs {
+            /// Resolves every entry point by its C name using `loadfn`. Entry points for which
+            /// `loadfn` returns null panic when called.
+            pub fn load_with(loadfn: |&str| -> *const c_void) -> FnPtrs {
+                FnPtrs {
+                    $($name: loadfn(stringify!($name)),)*
+                }
+            }
+
+            /// Fills the table with the symbols of the statically linked GL library.
+            #[cfg(feature = "static_link")]
+            pub fn load_static() -> FnPtrs {
+                let mut fns = UNLOADED;
+                $($(#[$attr])* { fns.$name = ffi::$name as *const c_void; })*
+                fns
+            }
+
+            $(#[inline]
+            pub unsafe fn $name(&self, $($arg: $ty),*) $(-> $ret)* {
+                if self.$name.is_null() {
+                    panic!("{} was not loaded", stringify!($name));
+                }
+                let f: extern "C" fn($($ty),*) $(-> $ret)* = mem::transmute(self.$name);
+                f($($arg),*)
+            })*
+        }
+
+        // The entry points as the `Gl` wrappers call them: under the `debug_gl` feature, each
+        // call is followed by a check of `glGetError` unless checks are suspended.
+        #[allow(non_snake_case)]
+        impl Dispatch {
+            $(#[inline]
+            unsafe fn $name(&self, $($arg: $ty),*) $(-> $ret)* {
+                let result = self.raw.$name($($arg),*);
+                if cfg!(feature = "debug_gl") && stringify!($name) != "glGetError" &&
+                        *self.suspended.get() == 0 {
+                    self.raw.check_call(stringify!($name), || vec![$(format!("{}", $arg)),*]);
+                }
+                result
+            })*
+        }
+
+        #[cfg(feature = "static_link")]
+        mod ffi {
+            use libc::{c_int, c_void};
+            use super::{GLbitfield, GLboolean, GLchar, GLclampf, GLeglImageOES, GLenum, GLfloat};
+            use super::{GLint, GLintptr, GLsizei, GLsizeiptr, GLubyte, GLuint, GLvoid};
 
-// Lower-level API
+            extern {
+                $($(#[$attr])* pub fn $name($($arg: $ty),*) $(-> $ret)*;)*
+            }
+        }
 
-pub fn glActiveTexture(texture: GLenum);
+        // Lower-level API, dispatching through the global context.
 
-pub fn glAttachShader(program: GLuint, shader: GLuint);
+        $(#[inline]
+        #[allow(non_snake_case)]
+        pub unsafe fn $name($($arg: $ty),*) $(-> $ret)* {
+            global().fns.raw.$name($($arg),*)
+        })*
+    }
+}
 
-pub fn glBindAttribLocation(program: GLuint, index: GLuint, name: *const GLchar);
+/// Called with the name, arguments and error code of every call through a `Gl` wrapper that
+/// fails when the `debug_gl` feature is enabled.
+pub type ErrorHook = fn(&str, &[String], GLenum);
 
-pub fn glBindBuffer(target: GLenum, buffer: GLuint);
+static mut ERROR_HOOK: Option<ErrorHook> = None;
 
-pub fn glBindFramebuffer(target: GLenum, framebuffer: GLuint);
+/// Installs the function called when a GL call fails under the `debug_gl` feature. Without a
+/// hook, failing calls panic.
+///
+/// The check reads `glGetError`, so an error it reports is no longer queued. The functions in
+/// `checked`, which read the queue themselves, suspend the check around their call; anything
+/// else that reads the queue, such as `error::drain_errors` after an unchecked call or a
+/// deliberate probe query, must suspend it with `Gl::suspend_debug_checks`.
+pub fn set_error_hook(hook: Option<ErrorHook>) {
+    unsafe {
+        ERROR_HOOK = hook;
+    }
+}
 
-pub fn glBindRenderbuffer(target: GLenum, renderbuffer: GLuint);
+impl FnPtrs {
+    // Checks `glGetError` after the call `name`, reporting any error to the installed hook.
+    unsafe fn check_call(&self, name: &str, args: || -> Vec<String>) {
+        let error = self.glGetError();
+        if error == NO_ERROR {
+            return
+        }
+        match ERROR_HOOK {
+            Some(hook) => hook(name, args().as_slice(), error),
+            None => panic!("{}({}) failed with GL error 0x{:04X}", name, args().connect(", "), error),
+        }
+    }
+}
 
-pub fn glBindTexture(target: GLenum, texture: GLuint);
+static mut GLOBAL: Gl = Gl {
+    fns: Dispatch {
+        raw: UNLOADED,
+        suspended: UnsafeCell { value: 0 },
+    },
+    pixel_store: UnsafeCell { value: INITIAL_PIXEL_STORE },
+    has_unpack_row_length: UnsafeCell { value: None },
+};
+static mut LOADED: bool = false;
 
-#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
-pub fn glBindVertexArray(array: GLuint);
+/// Loads the global context used by the free functions in this module, resolving every entry
+/// point by name with `loadfn`. This is typically a thin wrapper around `glXGetProcAddress`,
+/// `eglGetProcAddress` or a stub supplied by tests.
+pub fn load_with(loadfn: |&str| -> *const c_void) {
+    unsafe {
+        GLOBAL = Gl::load_with(loadfn);
+        LOADED = true;
+    }
+}
 
-pub fn glBlendColor(red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf);
+/// Returns the global context used by the free functions in this module.
+#[inline]
+pub fn global() -> &'static Gl {
+    unsafe {
+        if !LOADED {
+            load_default();
+        }
+        &GLOBAL
+    }
+}
 
-pub fn glBlendEquation(mode: GLenum);
+#[cfg(feature = "static_link")]
+unsafe fn load_default() {
+    GLOBAL = Gl::load_static();
+    LOADED = true;
+}
 
-pub fn glBlendEquationSeparate(modeRGB: GLenum, modeAlpha: GLenum);
+#[cfg(not(feature = "static_link"))]
+unsafe fn load_default() {
+    panic!("gl2::load_with must be called before any GL function");
+}
 
-pub fn glBlendFunc(sfactor: GLenum, dfactor: GLenum);
+// Invokes the macro `$m` with the declaration of every GL entry point.
+macro_rules! with_gl_functions {
+    ($m:ident) => {
+        $m! {
 
-pub fn glBlendFuncSeparate(srcRGB: GLenum, dstRGB: GLenum, srcAlpha: GLenum, dstAlpha: GLenum);
+        fn glActiveTexture(texture: GLenum);
 
-#[cfg(not(mac_10_6))]
-pub fn glBufferData(target: GLenum, size: GLsizeiptr, data: *const GLvoid, usage: GLenum);
+        fn glAttachShader(program: GLuint, shader: GLuint);
 
-pub fn glBufferSubData(target: GLenum, offset: GLintptr, size: GLsizeiptr, data: *const GLvoid);
+        fn glBindAttribLocation(program: GLuint, index: GLuint, name: *const GLchar);
 
-pub fn glCheckFramebufferStatus(target: GLenum) -> GLenum;
+        fn glBindBuffer(target: GLenum, buffer: GLuint);
 
-pub fn glClear(mask: GLbitfield);
+        fn glBindFramebuffer(target: GLenum, framebuffer: GLuint);
 
-pub fn glClearColor(red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf);
+        fn glBindRenderbuffer(target: GLenum, renderbuffer: GLuint);
 
-// Unsupported on Mac:
-//fn glClearDepthf(depth: GLclampf);
+        fn glBindTexture(target: GLenum, texture: GLuint);
 
-pub fn glClearStencil(s: GLint);
+        #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+        fn glBindVertexArray(array: GLuint);
 
-pub fn glColorMask(red: GLboolean, green: GLboolean, blue: GLboolean, alpha: GLboolean);
+        fn glBlendColor(red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf);
 
-pub fn glCompileShader(shader: GLuint);
+        fn glBlendEquation(mode: GLenum);
 
-pub fn glCompressedTexImage2D(target: GLenum, level: GLint, internalformat: GLenum, width: GLsizei, height: GLsizei, border: GLint, imageSize: GLsizei, data: *const GLvoid);
+        fn glBlendEquationSeparate(modeRGB: GLenum, modeAlpha: GLenum);
 
-pub fn glCompressedTexSubImage2D(target: GLenum, level: GLint, xoffset: GLint, yoffset: GLint, width: GLsizei, height: GLsizei, format: GLenum, imageSize: GLsizei, data: *const GLvoid);
+        fn glBlendFunc(sfactor: GLenum, dfactor: GLenum);
 
-pub fn glCopyTexImage2D(target: GLenum, level: GLint, internalformat: GLenum, x: GLint, y: GLint, width: GLsizei, height: GLsizei, border: GLint);
+        fn glBlendFuncSeparate(srcRGB: GLenum, dstRGB: GLenum, srcAlpha: GLenum, dstAlpha: GLenum);
 
-pub fn glCopyTexSubImage2D(target: GLenum, level: GLint, xoffset: GLint, yoffset: GLint, x: GLint, y: GLint, width: GLsizei, height: GLsizei);
+        #[cfg(not(mac_10_6))]
+        fn glBufferData(target: GLenum, size: GLsizeiptr, data: *const GLvoid, usage: GLenum);
 
-pub fn glCreateProgram() -> GLuint;
+        fn glBufferSubData(target: GLenum, offset: GLintptr, size: GLsizeiptr, data: *const GLvoid);
 
-pub fn glCreateShader(_type: GLenum) -> GLuint;
+        fn glCheckFramebufferStatus(target: GLenum) -> GLenum;
 
-pub fn glCullFace(mode: GLenum);
+        fn glClear(mask: GLbitfield);
 
-pub fn glDeleteBuffers(n: GLsizei, buffers: *const GLuint);
+        fn glClearColor(red: GLclampf, green: GLclampf, blue: GLclampf, alpha: GLclampf);
 
-pub fn glDeleteFramebuffers(n: GLsizei, framebuffers: *const GLuint);
+        // Unsupported on Mac:
+        //fn glClearDepthf(depth: GLclampf);
 
-pub fn glDeleteProgram(program: GLuint);
+        fn glClearStencil(s: GLint);
 
-pub fn glDeleteRenderbuffers(n: GLsizei, renderbuffers: *const GLuint);
+        fn glColorMask(red: GLboolean, green: GLboolean, blue: GLboolean, alpha: GLboolean);
 
-pub fn glDeleteShader(shader: GLuint);
+        fn glCompileShader(shader: GLuint);
 
-pub fn glDeleteTextures(n: GLsizei, textures: *const GLuint);
+        fn glCompressedTexImage2D(target: GLenum, level: GLint, internalformat: GLenum, width: GLsizei, height: GLsizei, border: GLint, imageSize: GLsizei, data: *const GLvoid);
 
-pub fn glDepthFunc(func: GLenum);
+        fn glCompressedTexSubImage2D(target: GLenum, level: GLint, xoffset: GLint, yoffset: GLint, width: GLsizei, height: GLsizei, format: GLenum, imageSize: GLsizei, data: *const GLvoid);
 
-pub fn glDepthMask(flag: GLboolean);
+        fn glCopyTexImage2D(target: GLenum, level: GLint, internalformat: GLenum, x: GLint, y: GLint, width: GLsizei, height: GLsizei, border: GLint);
 
-// Unsupported on Mac:
-//fn glDepthRangef(zNear: GLclampf, zFar: GLclampf);
+        fn glCopyTexSubImage2D(target: GLenum, level: GLint, xoffset: GLint, yoffset: GLint, x: GLint, y: GLint, width: GLsizei, height: GLsizei);
 
-pub fn glDetachShader(program: GLuint, shader: GLuint);
+        fn glCreateProgram() -> GLuint;
 
-pub fn glDisable(cap: GLenum);
+        fn glCreateShader(_type: GLenum) -> GLuint;
 
-pub fn glDisableVertexAttribArray(index: GLuint);
+        fn glCullFace(mode: GLenum);
 
-pub fn glDrawArrays(mode: GLenum, first: GLint, count: GLsizei);
+        fn glDeleteBuffers(n: GLsizei, buffers: *const GLuint);
 
-pub fn glDrawElements(mode: GLenum, count: GLsizei, _type: GLenum, indices: *const GLvoid);
+        fn glDeleteFramebuffers(n: GLsizei, framebuffers: *const GLuint);
 
-#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
-pub fn glDrawArraysInstanced(mode: GLenum, first: GLint, count: GLsizei, primcount: GLsizei);
+        fn glDeleteProgram(program: GLuint);
 
-#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
-pub fn glDrawElementsInstanced(mode: GLenum, count: GLsizei, _type: GLenum, indices: *const GLvoid, primcount: GLsizei);
+        fn glDeleteRenderbuffers(n: GLsizei, renderbuffers: *const GLuint);
 
-pub fn glEnable(cap: GLenum);
+        fn glDeleteShader(shader: GLuint);
 
-pub fn glEnableVertexAttribArray(index: GLuint);
+        fn glDeleteTextures(n: GLsizei, textures: *const GLuint);
 
-pub fn glFinish();
+        #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+        fn glDeleteVertexArrays(n: GLsizei, arrays: *const GLuint);
 
-pub fn glFlush();
+        fn glDepthFunc(func: GLenum);
 
-pub fn glFramebufferRenderbuffer(target: GLenum, attachment: GLenum, renderbuffertarget: GLenum, renderbuffer: GLuint);
+        fn glDepthMask(flag: GLboolean);
 
-pub fn glFramebufferTexture2D(target: GLenum, attachment: GLenum, textarget: GLenum, texture: GLuint, level: GLint);
+        // Unsupported on Mac:
+        //fn glDepthRangef(zNear: GLclampf, zFar: GLclampf);
 
-pub fn glFrontFace(mode: GLenum);
+        fn glDetachShader(program: GLuint, shader: GLuint);
 
-pub fn glGenBuffers(n: GLsizei, buffers: *mut GLuint);
+        fn glDisable(cap: GLenum);
 
-pub fn glGenerateMipmap(target: GLenum);
+        fn glDisableVertexAttribArray(index: GLuint);
 
-pub fn glGenFramebuffers(n: GLsizei, framebuffers: *mut GLuint);
+        fn glDrawArrays(mode: GLenum, first: GLint, count: GLsizei);
 
-pub fn glGenRenderbuffers(n: GLsizei, renderbuffers: *mut GLuint);
+        fn glDrawElements(mode: GLenum, count: GLsizei, _type: GLenum, indices: *const GLvoid);
 
-pub fn glGenTextures(n: GLsizei, textures: *mut GLuint);
+        #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+        fn glDrawArraysInstanced(mode: GLenum, first: GLint, count: GLsizei, primcount: GLsizei);
 
-#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
-pub fn glGenVertexArrays(n: GLsizei, arrays: *mut GLuint);
+        #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+        fn glDrawElementsInstanced(mode: GLenum, count: GLsizei, _type: GLenum, indices: *const GLvoid, primcount: GLsizei);
 
-pub fn glGetActiveAttrib(program: GLuint, index: GLuint, bufsize: GLsizei, length: *mut GLsizei, size: *mut GLint, _type: *mut GLenum, name: *mut GLchar);
+        fn glEnable(cap: GLenum);
 
-pub fn glGetActiveUniform(program: GLuint, index: GLuint, bufsize: GLsizei, length: *mut GLsizei, size: *mut GLint, _type: *mut GLenum, name: *mut GLchar);
+        fn glEnableVertexAttribArray(index: GLuint);
 
-pub fn glGetAttachedShaders(program: GLuint, maxcount: GLsizei, count: *mut GLsizei, shaders: *mut GLuint);
+        fn glFinish();
 
-pub fn glGetAttribLocation(program: GLuint, name: *const GLchar) -> c_int;
+        fn glFlush();
 
-pub fn glGetBooleanv(pname: GLenum, params: *mut GLboolean);
+        fn glFramebufferRenderbuffer(target: GLenum, attachment: GLenum, renderbuffertarget: GLenum, renderbuffer: GLuint);
 
-pub fn glGetBufferParameteriv(target: GLenum, pname: GLenum, params: *mut GLint);
+        fn glFramebufferTexture2D(target: GLenum, attachment: GLenum, textarget: GLenum, texture: GLuint, level: GLint);
 
-pub fn glGetError() -> GLenum;
+        fn glFrontFace(mode: GLenum);
 
-pub fn glGetFloatv(pname: GLenum, params: *mut GLfloat);
+        fn glGenBuffers(n: GLsizei, buffers: *mut GLuint);
 
-pub fn glGetFramebufferAttachmentParameteriv(target: GLenum, attachment: GLenum, pname: GLenum, params: *mut GLint);
+        fn glGenerateMipmap(target: GLenum);
 
-pub fn glGetIntegerv(pname: GLenum, params: *mut GLint);
+        fn glGenFramebuffers(n: GLsizei, framebuffers: *mut GLuint);
 
-pub fn glGetProgramiv(program: GLuint, pname: GLenum, params: *mut GLint);
+        fn glGenRenderbuffers(n: GLsizei, renderbuffers: *mut GLuint);
 
-pub fn glGetProgramInfoLog(program: GLuint, bufsize: GLsizei, length: *mut GLsizei, infolog: *mut GLchar);
+        fn glGenTextures(n: GLsizei, textures: *mut GLuint);
 
-pub fn glGetRenderbufferParameteriv(target: GLenum, pname: GLenum, params: *mut GLint);
+        #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+        fn glGenVertexArrays(n: GLsizei, arrays: *mut GLuint);
 
-pub fn glGetShaderiv(shader: GLuint, pname: GLenum, params: *mut GLint);
+        fn glGetActiveAttrib(program: GLuint, index: GLuint, bufsize: GLsizei, length: *mut GLsizei, size: *mut GLint, _type: *mut GLenum, name: *mut GLchar);
 
-pub fn glGetShaderInfoLog(shader: GLuint, bufsize: GLsizei, length: *mut GLsizei, infolog: *mut GLchar);
+        fn glGetActiveUniform(program: GLuint, index: GLuint, bufsize: GLsizei, length: *mut GLsizei, size: *mut GLint, _type: *mut GLenum, name: *mut GLchar);
 
-// Unsupported on Mac:
-//fn glGetShaderPrecisionFormat(shadertype: GLenum, precisiontype: GLenum, range: *GLint, precision: *GLint);
+        fn glGetAttachedShaders(program: GLuint, maxcount: GLsizei, count: *mut GLsizei, shaders: *mut GLuint);
 
-pub fn glGetShaderSource(shader: GLuint, bufsize: GLsizei, length: *mut GLsizei, source: *mut GLchar);
+        fn glGetAttribLocation(program: GLuint, name: *const GLchar) -> c_int;
 
-pub fn glGetString(name: GLenum) -> *const GLubyte;
+        fn glGetBooleanv(pname: GLenum, params: *mut GLboolean);
 
-pub fn glGetTexParameterfv(target: GLenum, pname: GLenum, params: *mut GLfloat);
+        fn glGetBufferParameteriv(target: GLenum, pname: GLenum, params: *mut GLint);
 
-pub fn glGetTexParameteriv(target: GLenum, pname: GLenum, params: *mut GLint);
+        fn glGetError() -> GLenum;
 
-pub fn glGetUniformfv(program: GLuint, location: GLint, params: *mut GLfloat);
+        fn glGetFloatv(pname: GLenum, params: *mut GLfloat);
 
-pub fn glGetUniformiv(program: GLuint, location: GLint, params: *mut GLint);
+        fn glGetFramebufferAttachmentParameteriv(target: GLenum, attachment: GLenum, pname: GLenum, params: *mut GLint);
 
-pub fn glGetUniformLocation(program: GLuint, name: *const GLchar) -> c_int;
+        fn glGetIntegerv(pname: GLenum, params: *mut GLint);
 
-pub fn glGetVertexAttribfv(index: GLuint, pname: GLenum, params: *mut GLfloat);
+        fn glGetProgramiv(program: GLuint, pname: GLenum, params: *mut GLint);
 
-pub fn glGetVertexAttribiv(index: GLuint, pname: GLenum, params: *mut GLint);
+        fn glGetProgramInfoLog(program: GLuint, bufsize: GLsizei, length: *mut GLsizei, infolog: *mut GLchar);
 
-pub fn glGetVertexAttribPointerv(index: GLuint, pname: GLenum, pointer: *mut *mut GLvoid);
+        fn glGetRenderbufferParameteriv(target: GLenum, pname: GLenum, params: *mut GLint);
 
-pub fn glHint(target: GLenum, mode: GLenum);
+        fn glGetShaderiv(shader: GLuint, pname: GLenum, params: *mut GLint);
 
-pub fn glIsBuffer(buffer: GLuint) -> GLboolean;
+        fn glGetShaderInfoLog(shader: GLuint, bufsize: GLsizei, length: *mut GLsizei, infolog: *mut GLchar);
 
-pub fn glIsEnabled(cap: GLenum) -> GLboolean;
+        // Unsupported on Mac:
+        //fn glGetShaderPrecisionFormat(shadertype: GLenum, precisiontype: GLenum, range: *GLint, precision: *GLint);
 
-pub fn glIsFramebuffer(framebuffer: GLuint) -> GLboolean;
+        fn glGetShaderSource(shader: GLuint, bufsize: GLsizei, length: *mut GLsizei, source: *mut GLchar);
 
-pub fn glIsProgram(program: GLuint) -> GLboolean;
+        fn glGetString(name: GLenum) -> *const GLubyte;
 
-pub fn glIsRenderbuffer(renderbuffer: GLuint) -> GLboolean;
+        fn glGetTexParameterfv(target: GLenum, pname: GLenum, params: *mut GLfloat);
 
-pub fn glIsShader(shader: GLuint) -> GLboolean;
+        fn glGetTexParameteriv(target: GLenum, pname: GLenum, params: *mut GLint);
 
-pub fn glIsTexture(texture: GLuint) -> GLboolean;
+        fn glGetUniformfv(program: GLuint, location: GLint, params: *mut GLfloat);
 
-pub fn glLineWidth(width: GLfloat);
+        fn glGetUniformiv(program: GLuint, location: GLint, params: *mut GLint);
 
-pub fn glLinkProgram(program: GLuint);
+        fn glGetUniformLocation(program: GLuint, name: *const GLchar) -> c_int;
 
-pub fn glPixelStorei(pname: GLenum, param: GLint);
+        fn glGetVertexAttribfv(index: GLuint, pname: GLenum, params: *mut GLfloat);
 
-pub fn glPolygonOffset(factor: GLfloat, units: GLfloat);
+        fn glGetVertexAttribiv(index: GLuint, pname: GLenum, params: *mut GLint);
 
-#[cfg(not(target_os="android"))]
-pub fn glPolygonMode(face: GLenum, mode: GLenum);
+        fn glGetVertexAttribPointerv(index: GLuint, pname: GLenum, pointer: *mut *mut GLvoid);
 
-pub fn glReadPixels(x: GLint, y: GLint, width: GLsizei, height: GLsizei, format: GLenum, _type: GLenum, pixels: *mut GLvoid);
+        fn glHint(target: GLenum, mode: GLenum);
 
-// Unsupported on Mac:
-// fn glReleaseShaderCompiler();
+        fn glIsBuffer(buffer: GLuint) -> GLboolean;
 
-pub fn glRenderbufferStorage(target: GLenum, internalformat: GLenum, width: GLsizei, height: GLsizei);
+        fn glIsEnabled(cap: GLenum) -> GLboolean;
 
-pub fn glSampleCoverage(value: GLclampf, invert: GLboolean);
+        fn glIsFramebuffer(framebuffer: GLuint) -> GLboolean;
 
-pub fn glScissor(x: GLint, y: GLint, width: GLsizei, height: GLsizei);
+        fn glIsProgram(program: GLuint) -> GLboolean;
 
-// Unsupported on Mac:
-//fn glShaderBinary(n: GLsizei, shaders: *GLuint, binaryformat: GLenum, binary: *GLvoid, length: GLsizei);
+        fn glIsRenderbuffer(renderbuffer: GLuint) -> GLboolean;
 
-pub fn glShaderSource(shader: GLuint, count: GLsizei, string: *const *const GLchar, length: *const GLint);
+        fn glIsShader(shader: GLuint) -> GLboolean;
 
-pub fn glStencilFunc(func: GLenum, reference: GLint, mask: GLuint);
+        fn glIsTexture(texture: GLuint) -> GLboolean;
 
-pub fn glStencilFuncSeparate(face: GLenum, func: GLenum, reference: GLint, mask: GLuint);
+        fn glLineWidth(width: GLfloat);
 
-pub fn glStencilMask(mask: GLuint);
+        fn glLinkProgram(program: GLuint);
 
-pub fn glStencilMaskSeparate(face: GLenum, mask: GLuint);
+        fn glPixelStorei(pname: GLenum, param: GLint);
 
-pub fn glStencilOp(_fail: GLenum, zfail: GLenum, zpass: GLenum);
+        fn glPolygonOffset(factor: GLfloat, units: GLfloat);
 
-pub fn glStencilOpSeparate(face: GLenum, _fail: GLenum, zfail: GLenum, zpass: GLenum);
+        #[cfg(not(target_os="android"))]
+        fn glPolygonMode(face: GLenum, mode: GLenum);
 
-pub fn glTexImage2D(target: GLenum, level: GLint, internalformat: GLint, width: GLsizei, height: GLsizei, border: GLint, format: GLenum, _type: GLenum, pixels: *const GLvoid);
+        fn glReadPixels(x: GLint, y: GLint, width: GLsizei, height: GLsizei, format: GLenum, _type: GLenum, pixels: *mut GLvoid);
 
-pub fn glTexParameterf(target: GLenum, pname: GLenum, param: GLfloat);
+        // Unsupported on Mac:
+        // fn glReleaseShaderCompiler();
 
-pub fn glTexParameterfv(target: GLenum, pname: GLenum, params: *mut GLfloat);
+        fn glRenderbufferStorage(target: GLenum, internalformat: GLenum, width: GLsizei, height: GLsizei);
 
-pub fn glTexParameteri(target: GLenum, pname: GLenum, param: GLint);
+        fn glSampleCoverage(value: GLclampf, invert: GLboolean);
 
-pub fn glTexParameteriv(target: GLenum, pname: GLenum, params: *mut GLint);
+        fn glScissor(x: GLint, y: GLint, width: GLsizei, height: GLsizei);
 
-pub fn glTexSubImage2D(target: GLenum, level: GLint, xoffset: GLint, yoffset: GLint, width: GLsizei, height: GLsizei, format: GLenum, _type: GLenum, pixels: *const GLvoid);
+        // Unsupported on Mac:
+        //fn glShaderBinary(n: GLsizei, shaders: *GLuint, binaryformat: GLenum, binary: *GLvoid, length: GLsizei);
 
-pub fn glUniform1f(location: GLint, x: GLfloat);
+        fn glShaderSource(shader: GLuint, count: GLsizei, string: *const *const GLchar, length: *const GLint);
 
-pub fn glUniform1fv(location: GLint, count: GLsizei, v: *mut GLfloat);
+        fn glStencilFunc(func: GLenum, reference: GLint, mask: GLuint);
 
-pub fn glUniform1i(location: GLint, x: GLint);
+        fn glStencilFuncSeparate(face: GLenum, func: GLenum, reference: GLint, mask: GLuint);
 
-pub fn glUniform1iv(location: GLint, count: GLsizei, v: *mut GLint);
+        fn glStencilMask(mask: GLuint);
 
-pub fn glUniform2f(location: GLint, x: GLfloat, y: GLfloat);
+        fn glStencilMaskSeparate(face: GLenum, mask: GLuint);
 
-pub fn glUniform2fv(location: GLint, count: GLsizei, v: *mut GLfloat);
+        fn glStencilOp(_fail: GLenum, zfail: GLenum, zpass: GLenum);
 
-pub fn glUniform2i(location: GLint, x: GLint, y: GLint);
+        fn glStencilOpSeparate(face: GLenum, _fail: GLenum, zfail: GLenum, zpass: GLenum);
 
-pub fn glUniform2iv(location: GLint, count: GLsizei, v: *mut GLint);
+        fn glTexImage2D(target: GLenum, level: GLint, internalformat: GLint, width: GLsizei, height: GLsizei, border: GLint, format: GLenum, _type: GLenum, pixels: *const GLvoid);
 
-pub fn glUniform3f(location: GLint, x: GLfloat, y: GLfloat, z: GLfloat);
+        fn glTexParameterf(target: GLenum, pname: GLenum, param: GLfloat);
 
-pub fn glUniform3fv(location: GLint, count: GLsizei, v: *mut GLfloat);
+        fn glTexParameterfv(target: GLenum, pname: GLenum, params: *mut GLfloat);
 
-pub fn glUniform3i(location: GLint, x: GLint, y: GLint, z: GLint);
+        fn glTexParameteri(target: GLenum, pname: GLenum, param: GLint);
 
-pub fn glUniform3iv(location: GLint, count: GLsizei, v: *mut GLint);
+        fn glTexParameteriv(target: GLenum, pname: GLenum, params: *mut GLint);
 
-pub fn glUniform4f(location: GLint, x: GLfloat, y: GLfloat, z: GLfloat, w: GLfloat);
+        fn glTexSubImage2D(target: GLenum, level: GLint, xoffset: GLint, yoffset: GLint, width: GLsizei, height: GLsizei, format: GLenum, _type: GLenum, pixels: *const GLvoid);
 
-pub fn glUniform4fv(location: GLint, count: GLsizei, v: *mut GLfloat);
+        fn glUniform1f(location: GLint, x: GLfloat);
 
-pub fn glUniform4i(location: GLint, x: GLint, y: GLint, z: GLint, w: GLint);
+        fn glUniform1fv(location: GLint, count: GLsizei, v: *mut GLfloat);
 
-pub fn glUniform4iv(location: GLint, count: GLsizei, v: *mut GLint);
+        fn glUniform1i(location: GLint, x: GLint);
 
-pub fn glUniformMatrix2fv(location: GLint, count: GLsizei, transpose: GLboolean, value: *mut GLfloat);
+        fn glUniform1iv(location: GLint, count: GLsizei, v: *mut GLint);
 
-pub fn glUniformMatrix3fv(location: GLint, count: GLsizei, transpose: GLboolean, value: *mut GLfloat);
+        fn glUniform2f(location: GLint, x: GLfloat, y: GLfloat);
 
-pub fn glUniformMatrix4fv(location: GLint, count: GLsizei, transpose: GLboolean, value: *mut GLfloat);
+        fn glUniform2fv(location: GLint, count: GLsizei, v: *mut GLfloat);
 
-pub fn glUseProgram(program: GLuint);
+        fn glUniform2i(location: GLint, x: GLint, y: GLint);
 
-pub fn glValidateProgram(program: GLuint);
+        fn glUniform2iv(location: GLint, count: GLsizei, v: *mut GLint);
 
-pub fn glVertexAttrib1f(indx: GLuint, x: GLfloat);
+        fn glUniform3f(location: GLint, x: GLfloat, y: GLfloat, z: GLfloat);
 
-pub fn glVertexAttrib1fv(indx: GLuint, values: *mut GLfloat);
+        fn glUniform3fv(location: GLint, count: GLsizei, v: *mut GLfloat);
 
-pub fn glVertexAttrib2f(indx: GLuint, x: GLfloat, y: GLfloat);
+        fn glUniform3i(location: GLint, x: GLint, y: GLint, z: GLint);
 
-pub fn glVertexAttrib2fv(indx: GLuint, values: *mut GLfloat);
+        fn glUniform3iv(location: GLint, count: GLsizei, v: *mut GLint);
 
-pub fn glVertexAttrib3f(indx: GLuint, x: GLfloat, y: GLfloat, z: GLfloat);
+        fn glUniform4f(location: GLint, x: GLfloat, y: GLfloat, z: GLfloat, w: GLfloat);
 
-pub fn glVertexAttrib3fv(indx: GLuint, values: *mut GLfloat);
+        fn glUniform4fv(location: GLint, count: GLsizei, v: *mut GLfloat);
 
-pub fn glVertexAttrib4f(indx: GLuint, x: GLfloat, y: GLfloat, z: GLfloat, w: GLfloat);
+        fn glUniform4i(location: GLint, x: GLint, y: GLint, z: GLint, w: GLint);
 
-pub fn glVertexAttrib4fv(indx: GLuint, values: *mut GLfloat);
+        fn glUniform4iv(location: GLint, count: GLsizei, v: *mut GLint);
 
-pub fn glVertexAttribPointer(indx: GLuint, size: GLint, _type: GLenum, normalized: GLboolean, stride: GLsizei, ptr: *const GLvoid);
+        fn glUniformMatrix2fv(location: GLint, count: GLsizei, transpose: GLboolean, value: *mut GLfloat);
 
-#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
-pub fn glVertexAttribDivisor(indx: GLuint, divisor: GLuint);
+        fn glUniformMatrix3fv(location: GLint, count: GLsizei, transpose: GLboolean, value: *mut GLfloat);
 
-pub fn glViewport(x: GLint, y: GLint, width: GLsizei, height: GLsizei);
+        fn glUniformMatrix4fv(location: GLint, count: GLsizei, transpose: GLboolean, value: *mut GLfloat);
 
-#[cfg(target_os="android")]
-pub fn glEGLImageTargetTexture2DOES(target: GLenum, image: GLeglImageOES);
-pub fn glEGLImageTargetRenderbufferStorageOES(target: GLenum, image: GLeglImageOES);
+        fn glUseProgram(program: GLuint);
 
-}
+        fn glValidateProgram(program: GLuint);
 
-// Apple extensions
-#[cfg(target_os="macos")]
-extern {
+        fn glVertexAttrib1f(indx: GLuint, x: GLfloat);
+
+        fn glVertexAttrib1fv(indx: GLuint, values: *mut GLfloat);
 
-pub fn glTextureRangeAPPLE(target: GLenum, length: GLsizei, pointer: *const GLvoid);
+        fn glVertexAttrib2f(indx: GLuint, x: GLfloat, y: GLfloat);
 
+        fn glVertexAttrib2fv(indx: GLuint, values: *mut GLfloat);
+
+        fn glVertexAttrib3f(indx: GLuint, x: GLfloat, y: GLfloat, z: GLfloat);
+
+        fn glVertexAttrib3fv(indx: GLuint, values: *mut GLfloat);
+
+        fn glVertexAttrib4f(indx: GLuint, x: GLfloat, y: GLfloat, z: GLfloat, w: GLfloat);
+
+        fn glVertexAttrib4fv(indx: GLuint, values: *mut GLfloat);
+
+        fn glVertexAttribPointer(indx: GLuint, size: GLint, _type: GLenum, normalized: GLboolean, stride: GLsizei, ptr: *const GLvoid);
+
+        #[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+        fn glVertexAttribDivisor(indx: GLuint, divisor: GLuint);
+
+        fn glViewport(x: GLint, y: GLint, width: GLsizei, height: GLsizei);
+
+        #[cfg(target_os="android")]
+        fn glEGLImageTargetTexture2DOES(target: GLenum, image: GLeglImageOES);
+
+        #[cfg(target_os="android")]
+        fn glEGLImageTargetRenderbufferStorageOES(target: GLenum, image: GLeglImageOES);
+
+        // Apple extensions
+        #[cfg(target_os="macos")]
+        fn glTextureRangeAPPLE(target: GLenum, length: GLsizei, pointer: *const GLvoid);
+
+        }
+    }
 }
 
+with_gl_functions!(gl_functions);
+
+#[cfg(test)]
+mod test {
+    use error::{IndexCountError, ParameterError, PixelError, UniformLengthError};
+    use error::VertexAttribError;
+    use mock;
+    use super::{COMPRESSED_RGB8_ETC2, ETC1_RGB8_OES, GLint, LINEAR, PACK_ALIGNMENT, RGB, RGBA};
+    use super::{LUMINANCE, TEXTURE_2D, TEXTURE_MIN_FILTER, TRIANGLES, UNPACK_ALIGNMENT};
+    use super::{UNPACK_ROW_LENGTH, UNSIGNED_BYTE};
+
+    #[test]
+    fn uploads_are_checked_against_the_whole_image() {
+        mock::reset();
+        let gl = mock::load();
+        let data = [0u8, ..796];
+        assert_eq!(gl.tex_image_2d(TEXTURE_2D, 0, RGBA as GLint, 100, 100, 0, RGBA, UNSIGNED_BYTE,
+                                   Some(data.as_slice())),
+                   Err(PixelError::BufferTooSmall {
+                       required: 40000,
+                       actual: 796,
+                   }));
+        // The tracked state is used, so nothing is asked of the context.
+        assert!(mock::calls().is_empty());
+    }
+
+    #[test]
+    fn rejected_alignment_is_not_tracked() {
+        mock::reset();
+        let gl = mock::load();
+        gl.pixel_store_i(UNPACK_ALIGNMENT, 1);
+        // GL rejects an alignment of 3, leaving it at 1.
+        gl.pixel_store_i(UNPACK_ALIGNMENT, 3);
+        assert_eq!(gl.pixel_store().unpack_alignment, 1);
+        let data = [0u8, ..9];
+        assert_eq!(gl.tex_sub_image_2d(TEXTURE_2D, 0, 0, 0, 3, 3, LUMINANCE, UNSIGNED_BYTE,
+                                       Some(data.as_slice())),
+                   Ok(()));
+    }
+
+    #[cfg(not(target_os="android"))]
+    #[test]
+    fn row_length_widens_the_stride() {
+        mock::reset();
+        let gl = mock::load();
+        gl.pixel_store_i(UNPACK_ALIGNMENT, 1);
+        gl.pixel_store_i(UNPACK_ROW_LENGTH, 200);
+        let data = [0u8, ..200 * 4 + 8];
+        assert_eq!(gl.tex_sub_image_2d(TEXTURE_2D, 0, 0, 0, 2, 2, RGBA, UNSIGNED_BYTE,
+                                       Some(data.slice_to(200 * 4 + 7))),
+                   Err(PixelError::BufferTooSmall {
+                       required: 200 * 4 + 8,
+                       actual: 200 * 4 + 7,
+                   }));
+        assert_eq!(gl.tex_sub_image_2d(TEXTURE_2D, 0, 0, 0, 2, 2, RGBA, UNSIGNED_BYTE,
+                                       Some(data.as_slice())),
+                   Ok(()));
+    }
+
+    #[test]
+    fn refresh_reads_the_context() {
+        mock::reset();
+        let gl = mock::load();
+        mock::push_return("glGetIntegerv", 2);
+        mock::push_return("glGetIntegerv", 8);
+        mock::push_return("glGetIntegerv", 16);
+        let store = gl.refresh_pixel_store();
+        assert_eq!(store.pack_alignment, 2);
+        assert_eq!(store.unpack_alignment, 8);
+        if !cfg!(target_os="android") {
+            assert_eq!(store.unpack_row_length, 16);
+        }
+        assert_eq!(gl.pixel_store(), store);
+    }
+
+    #[test]
+    fn short_parameter_arrays_are_rejected() {
+        mock::reset();
+        let gl = mock::load();
+        // `TEXTURE_BORDER_COLOR`, which has four values on desktop GL.
+        assert_eq!(gl.tex_parameter_fv(TEXTURE_2D, 0x1004, &[0.0]), Err(ParameterError {
+            pname: 0x1004,
+            required: 4,
+            actual: 1,
+        }));
+        assert_eq!(gl.tex_parameter_iv(TEXTURE_2D, TEXTURE_MIN_FILTER, &[]), Err(ParameterError {
+            pname: TEXTURE_MIN_FILTER,
+            required: 1,
+            actual: 0,
+        }));
+        assert!(mock::calls().is_empty());
+        assert_eq!(gl.tex_parameter_iv(TEXTURE_2D, TEXTURE_MIN_FILTER, &[LINEAR as GLint]), Ok(()));
+        assert_eq!(mock::calls().len(), 1);
+    }
+
+    #[test]
+    fn etc1_cannot_be_updated_in_part() {
+        mock::reset();
+        let gl = mock::load();
+        let block = [0u8, ..8];
+        assert_eq!(gl.compressed_tex_sub_image_2d(TEXTURE_2D, 0, 0, 0, 4, 4, ETC1_RGB8_OES,
+                                                  block.as_slice()),
+                   Err(PixelError::UnsupportedFormat(ETC1_RGB8_OES)));
+        assert!(mock::calls().is_empty());
+        assert_eq!(gl.compressed_tex_sub_image_2d(TEXTURE_2D, 0, 0, 0, 4, 4, COMPRESSED_RGB8_ETC2,
+                                                  block.as_slice()),
+                   Ok(()));
+    }
+
+    #[test]
+    fn read_pixels_packs_rows_tightly() {
+        mock::reset();
+        let gl = mock::load();
+        let pixels = gl.read_pixels(0, 0, 3, 2, RGB, UNSIGNED_BYTE).unwrap();
+        assert_eq!(pixels.len(), 3 * 3 * 2);
+
+        let alignments: Vec<String> = mock::calls().into_iter().filter(|call| {
+            call.name == "glPixelStorei" && call.args[0] == format!("{}", PACK_ALIGNMENT)
+        }).map(|call| call.args[1].clone()).collect();
+        assert_eq!(alignments, vec!["1".to_string(), "4".to_string()]);
+        assert_eq!(gl.pixel_store().pack_alignment, 4);
+    }
+
+    #[test]
+    fn uniform_data_must_be_whole_elements() {
+        mock::reset();
+        let gl = mock::load();
+        assert_eq!(gl.uniform_matrix_2fv(0, false, &[1.0, 0.0, 0.0]), Err(UniformLengthError {
+            len: 3,
+            components: 4,
+        }));
+        assert!(mock::calls().is_empty());
+        assert_eq!(gl.uniform_3iv(0, &[1, 2, 3, 4, 5, 6]), Ok(()));
+        assert_eq!(mock::calls()[0].args[1].as_slice(), "2");
+    }
+
+    #[test]
+    fn index_count_must_fit_the_slice() {
+        mock::reset();
+        let gl = mock::load();
+        let indices = [0u16, 1, 2];
+        assert_eq!(gl.draw_elements_indexed(TRIANGLES, 4, indices.as_slice()),
+                   Err(IndexCountError::TooMany {
+                       count: 4,
+                       len: 3,
+                   }));
+        assert_eq!(gl.draw_elements_indexed(TRIANGLES, -1, indices.as_slice()),
+                   Err(IndexCountError::Negative(-1)));
+        assert!(mock::calls().is_empty());
+        assert_eq!(gl.draw_elements_indexed(TRIANGLES, 3, indices.as_slice()), Ok(()));
+    }
+
+    #[test]
+    fn index_offset_must_be_aligned() {
+        mock::reset();
+        let gl = mock::load();
+        assert_eq!(gl.draw_elements_offset::<u16>(TRIANGLES, 3, 3),
+                   Err(IndexCountError::MisalignedOffset {
+                       offset: 3,
+                       index_size: 2,
+                   }));
+        assert_eq!(gl.draw_elements_offset::<u16>(TRIANGLES, -1, 0),
+                   Err(IndexCountError::Negative(-1)));
+        assert!(mock::calls().is_empty());
+        assert_eq!(gl.draw_elements_offset::<u16>(TRIANGLES, 3, 4), Ok(()));
+    }
+
+    #[test]
+    fn vertex_attrib_pointer_checks_size_and_stride() {
+        mock::reset();
+        let gl = mock::load();
+        assert_eq!(gl.vertex_attrib_pointer::<f32>(0, 5, false, 0, 0),
+                   Err(VertexAttribError::InvalidSize(5)));
+        assert_eq!(gl.vertex_attrib_pointer::<f32>(0, 0, false, 0, 0),
+                   Err(VertexAttribError::InvalidSize(0)));
+        assert_eq!(gl.vertex_attrib_pointer::<f32>(0, 3, false, -4, 0),
+                   Err(VertexAttribError::NegativeStride(-4)));
+        assert!(mock::calls().is_empty());
+        assert_eq!(gl.vertex_attrib_pointer::<f32>(0, 3, false, 12, 0), Ok(()));
+    }
+}
@@ -10,6 +10,25 @@
 #![crate_name = "opengles"]
 #![crate_type = "rlib"]
 
+#![feature(globs, macro_rules, unsafe_destructor)]
+
 extern crate libc;
 
+#[macro_escape]
 pub mod gl2;
+pub mod checked;
+pub mod compressed;
+pub mod copy;
+pub mod cube_map;
+pub mod enums;
+pub mod error;
+pub mod framebuffer;
+pub mod mock;
+pub mod objects;
+pub mod pixels;
+pub mod reflection;
+pub mod sampler;
+pub mod stencil;
+pub mod typed;
+pub mod uniform;
+pub mod vertex;
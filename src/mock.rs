@@ -0,0 +1,511 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A recording GL backend that can stand in for the real driver, so code calling `gl2` can be
+//! tested on a machine without a GPU.
+//!
+//! Every call made through a context loaded from this backend is appended to a log with its
+//! arguments. `glGen*`, `glCreateProgram` and `glCreateShader` hand out fresh object names,
+//! `glDelete*` records the names it is given rather than the pointer to them, and the value
+//! returned by (or written to the output parameters of) the entry points listed under
+//! `push_return` and `push_string` can be scripted. Every other entry point returns zero.
+//! The log and the scripted values are per thread.
+//!
+//! Build with `--no-default-features` to avoid linking against the platform GL library.
+
+use gl2::{Gl, GLuint};
+use libc::c_void;
+use std::cell::RefCell;
+use std::collections::{HashMap, RingBuf};
+
+/// A GL call recorded by the mock backend.
+#[deriving(Clone, PartialEq, Show)]
+pub struct Call {
+    /// The C name of the entry point, e.g. `glBindTexture`.
+    pub name: &'static str,
+    /// The arguments, formatted with `{}`.
+    pub args: Vec<String>,
+}
+
+struct State {
+    calls: Vec<Call>,
+    last_name: GLuint,
+    returns: HashMap<&'static str, RingBuf<i64>>,
+    strings: HashMap<&'static str, RingBuf<String>>,
+}
+
+impl State {
+    fn new() -> State {
+        State {
+            calls: Vec::new(),
+            last_name: 0,
+            returns: HashMap::new(),
+            strings: HashMap::new(),
+        }
+    }
+}
+
+thread_local!(static STATE: RefCell<State> = RefCell::new(State::new()));
+
+/// Returns a context whose entry points all record into this thread's log.
+pub fn load() -> Gl {
+    Gl::load_with(|name| get_proc_address(name))
+}
+
+/// Makes the global context used by the free functions in `gl2` record into this thread's log.
+pub fn load_global() {
+    ::gl2::load_with(|name| get_proc_address(name))
+}
+
+/// Resolves `name` to the mock implementation of that entry point, or null if there is none.
+pub fn get_proc_address(name: &str) -> *const c_void {
+    let f = overrides::lookup(name);
+    if !f.is_null() {
+        return f
+    }
+    stubs::lookup(name)
+}
+
+/// Clears the log, the scripted values and the object name counter.
+pub fn reset() {
+    STATE.with(|state| *state.borrow_mut() = State::new())
+}
+
+/// Returns the calls recorded so far.
+pub fn calls() -> Vec<Call> {
+    STATE.with(|state| state.borrow().calls.clone())
+}
+
+/// Returns the calls recorded so far and clears the log.
+pub fn take_calls() -> Vec<Call> {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        let calls = state.calls.clone();
+        state.calls.clear();
+        calls
+    })
+}
+
+/// Queues `value` as the result of the next call to the entry point `name`, e.g.
+/// `push_return("glGetError", INVALID_ENUM as i64)`. Once the queue is empty calls return zero,
+/// except for `glCheckFramebufferStatus`, which returns `FRAMEBUFFER_COMPLETE`, and the object
+/// creation functions, which return fresh names.
+///
+/// Of the entry points that return their result through a pointer, these can be scripted:
+///
+/// * `glGetBooleanv`, `glGetFloatv`, `glGetIntegerv`, `glGetBufferParameteriv`,
+///   `glGetFramebufferAttachmentParameteriv`, `glGetProgramiv`, `glGetRenderbufferParameteriv`,
+///   `glGetShaderiv`, `glGetTexParameter*v`, `glGetUniform*v` and `glGetVertexAttrib*v` write
+///   one value to the first element.
+/// * `glGetAttachedShaders` writes every queued value, up to the size of the buffer.
+/// * `glGetActiveAttrib` and `glGetActiveUniform` write two values, the size and then the type,
+///   along with a name queued with `push_string`.
+pub fn push_return(name: &'static str, value: i64) {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if !state.returns.contains_key(&name) {
+            state.returns.insert(name, RingBuf::new());
+        }
+        state.returns.get_mut(&name).unwrap().push_back(value);
+    })
+}
+
+/// Queues `value` as the text written by the next call to the entry point `name`, which is one
+/// of `glGetActiveAttrib`, `glGetActiveUniform`, `glGetProgramInfoLog` and `glGetShaderInfoLog`.
+/// Text longer than the caller's buffer is truncated as GL truncates it. Once the queue is empty
+/// calls write an empty string.
+pub fn push_string(name: &'static str, value: &str) {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        if !state.strings.contains_key(&name) {
+            state.strings.insert(name, RingBuf::new());
+        }
+        state.strings.get_mut(&name).unwrap().push_back(value.to_string());
+    })
+}
+
+fn record(name: &'static str, args: Vec<String>) {
+    STATE.with(|state| {
+        state.borrow_mut().calls.push(Call {
+            name: name,
+            args: args,
+        })
+    })
+}
+
+fn scripted_return(name: &'static str) -> Option<i64> {
+    STATE.with(|state| {
+        match state.borrow_mut().returns.get_mut(&name) {
+            Some(queue) => queue.pop_front(),
+            None => None,
+        }
+    })
+}
+
+fn next_return(name: &'static str) -> i64 {
+    scripted_return(name).unwrap_or(0)
+}
+
+fn next_string(name: &'static str) -> String {
+    STATE.with(|state| {
+        match state.borrow_mut().strings.get_mut(&name) {
+            Some(queue) => queue.pop_front().unwrap_or(String::new()),
+            None => String::new(),
+        }
+    })
+}
+
+fn next_name(name: &'static str) -> GLuint {
+    match scripted_return(name) {
+        Some(value) => value as GLuint,
+        None => {
+            STATE.with(|state| {
+                let mut state = state.borrow_mut();
+                state.last_name += 1;
+                state.last_name
+            })
+        }
+    }
+}
+
+macro_rules! mock_functions {
+    ($($(#[$attr:meta])* fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)*;)*) => {
+        // One recording stub for every entry point.
+        mod stubs {
+            use super::{record, next_return};
+            use libc::{c_int, c_void};
+            use gl2::{GLbitfield, GLboolean, GLchar, GLclampf, GLeglImageOES, GLenum, GLfloat};
+            use gl2::{GLint, GLintptr, GLsizei, GLsizeiptr, GLubyte, GLuint, GLvoid};
+
+            $(#[allow(non_snake_case)]
+            pub extern "C" fn $name($($arg: $ty),*) $(-> $ret)* {
+                record(stringify!($name), vec![$(format!("{}", $arg)),*]);
+                $(next_return(stringify!($name)) as $ret)*
+            })*
+
+            pub fn lookup(name: &str) -> *const c_void {
+                $(if name == stringify!($name) {
+                    return $name as *const c_void
+                })*
+                0 as *const c_void
+            }
+        }
+    }
+}
+
+with_gl_functions!(mock_functions);
+
+// Entry points whose stubs need to do more than return a scripted value.
+mod overrides {
+    use super::{record, next_name, next_return, next_string, scripted_return};
+    use gl2::{FRAMEBUFFER_COMPLETE, GLboolean, GLchar, GLenum, GLfloat, GLint, GLsizei, GLubyte};
+    use gl2::GLuint;
+    use libc::c_void;
+    use std::cmp;
+    use std::ptr;
+
+    macro_rules! gen_names {
+        ($($name:ident),*) => {
+            $(#[allow(non_snake_case)]
+            extern "C" fn $name(n: GLsizei, names: *mut GLuint) {
+                record(stringify!($name), vec![format!("{}", n), format!("{}", names)]);
+                for i in range(0, n as int) {
+                    unsafe {
+                        *names.offset(i) = next_name(stringify!($name));
+                    }
+                }
+            })*
+        }
+    }
+
+    gen_names!(glGenBuffers, glGenFramebuffers, glGenRenderbuffers, glGenTextures,
+               glGenVertexArrays);
+
+    macro_rules! delete_names {
+        ($($name:ident),*) => {
+            $(#[allow(non_snake_case)]
+            extern "C" fn $name(n: GLsizei, names: *const GLuint) {
+                let mut args = vec![format!("{}", n)];
+                for i in range(0, n as int) {
+                    args.push(format!("{}", unsafe { *names.offset(i) }));
+                }
+                record(stringify!($name), args);
+            })*
+        }
+    }
+
+    delete_names!(glDeleteBuffers, glDeleteFramebuffers, glDeleteRenderbuffers, glDeleteTextures,
+                  glDeleteVertexArrays);
+
+    macro_rules! get_v {
+        ($($name:ident($object:ty, $pname:ty) -> $ty:ty),*) => {
+            $(#[allow(non_snake_case)]
+            extern "C" fn $name(object: $object, pname: $pname, params: *mut $ty) {
+                record(stringify!($name),
+                       vec![format!("{}", object), format!("{}", pname), format!("{}", params)]);
+                unsafe {
+                    *params = next_return(stringify!($name)) as $ty;
+                }
+            })*
+        }
+    }
+
+    get_v!(glGetBufferParameteriv(GLenum, GLenum) -> GLint,
+           glGetProgramiv(GLuint, GLenum) -> GLint,
+           glGetRenderbufferParameteriv(GLenum, GLenum) -> GLint,
+           glGetShaderiv(GLuint, GLenum) -> GLint,
+           glGetTexParameterfv(GLenum, GLenum) -> GLfloat,
+           glGetTexParameteriv(GLenum, GLenum) -> GLint,
+           glGetUniformfv(GLuint, GLint) -> GLfloat,
+           glGetUniformiv(GLuint, GLint) -> GLint,
+           glGetVertexAttribfv(GLuint, GLenum) -> GLfloat,
+           glGetVertexAttribiv(GLuint, GLenum) -> GLint);
+
+    macro_rules! get_state_v {
+        ($($name:ident: $ty:ty),*) => {
+            $(#[allow(non_snake_case)]
+            extern "C" fn $name(pname: GLenum, params: *mut $ty) {
+                record(stringify!($name), vec![format!("{}", pname), format!("{}", params)]);
+                unsafe {
+                    *params = next_return(stringify!($name)) as $ty;
+                }
+            })*
+        }
+    }
+
+    get_state_v!(glGetBooleanv: GLboolean, glGetFloatv: GLfloat, glGetIntegerv: GLint);
+
+    #[allow(non_snake_case)]
+    extern "C" fn glGetFramebufferAttachmentParameteriv(target: GLenum,
+                                                        attachment: GLenum,
+                                                        pname: GLenum,
+                                                        params: *mut GLint) {
+        record("glGetFramebufferAttachmentParameteriv",
+               vec![format!("{}", target), format!("{}", attachment), format!("{}", pname),
+                    format!("{}", params)]);
+        unsafe {
+            *params = next_return("glGetFramebufferAttachmentParameteriv") as GLint;
+        }
+    }
+
+    #[allow(non_snake_case)]
+    extern "C" fn glGetAttachedShaders(program: GLuint,
+                                       max_count: GLsizei,
+                                       count: *mut GLsizei,
+                                       shaders: *mut GLuint) {
+        record("glGetAttachedShaders",
+               vec![format!("{}", program), format!("{}", max_count), format!("{}", count),
+                    format!("{}", shaders)]);
+        let mut written = 0;
+        while written < max_count {
+            match scripted_return("glGetAttachedShaders") {
+                Some(shader) => unsafe { *shaders.offset(written as int) = shader as GLuint },
+                None => break,
+            }
+            written += 1;
+        }
+        if !count.is_null() {
+            unsafe {
+                *count = written;
+            }
+        }
+    }
+
+    // Writes the next queued string for `name` the way GL writes text: truncated to fit
+    // `buf_size` with its terminating NUL, with the length written excluding the NUL.
+    unsafe fn write_string(name: &'static str,
+                           buf_size: GLsizei,
+                           length: *mut GLsizei,
+                           buf: *mut GLchar) {
+        let text = next_string(name);
+        if buf_size <= 0 {
+            return
+        }
+        let len = cmp::min(text.len(), buf_size as uint - 1);
+        ptr::copy_nonoverlapping_memory(buf as *mut u8, text.as_bytes().as_ptr(), len);
+        *buf.offset(len as int) = 0;
+        if !length.is_null() {
+            *length = len as GLsizei;
+        }
+    }
+
+    macro_rules! get_active {
+        ($($name:ident),*) => {
+            $(#[allow(non_snake_case)]
+            extern "C" fn $name(program: GLuint,
+                                index: GLuint,
+                                buf_size: GLsizei,
+                                length: *mut GLsizei,
+                                size: *mut GLint,
+                                ty: *mut GLenum,
+                                name: *mut GLchar) {
+                record(stringify!($name),
+                       vec![format!("{}", program), format!("{}", index),
+                            format!("{}", buf_size), format!("{}", length), format!("{}", size),
+                            format!("{}", ty), format!("{}", name)]);
+                unsafe {
+                    *size = next_return(stringify!($name)) as GLint;
+                    *ty = next_return(stringify!($name)) as GLenum;
+                    write_string(stringify!($name), buf_size, length, name);
+                }
+            })*
+        }
+    }
+
+    get_active!(glGetActiveAttrib, glGetActiveUniform);
+
+    macro_rules! get_info_log {
+        ($($name:ident),*) => {
+            $(#[allow(non_snake_case)]
+            extern "C" fn $name(object: GLuint,
+                                buf_size: GLsizei,
+                                length: *mut GLsizei,
+                                info_log: *mut GLchar) {
+                record(stringify!($name),
+                       vec![format!("{}", object), format!("{}", buf_size),
+                            format!("{}", length), format!("{}", info_log)]);
+                unsafe {
+                    write_string(stringify!($name), buf_size, length, info_log);
+                }
+            })*
+        }
+    }
+
+    get_info_log!(glGetProgramInfoLog, glGetShaderInfoLog);
+
+    #[allow(non_snake_case)]
+    extern "C" fn glCreateProgram() -> GLuint {
+        record("glCreateProgram", vec![]);
+        next_name("glCreateProgram")
+    }
+
+    #[allow(non_snake_case)]
+    extern "C" fn glCreateShader(shader_type: GLenum) -> GLuint {
+        record("glCreateShader", vec![format!("{}", shader_type)]);
+        next_name("glCreateShader")
+    }
+
+    #[allow(non_snake_case)]
+    extern "C" fn glCheckFramebufferStatus(target: GLenum) -> GLenum {
+        record("glCheckFramebufferStatus", vec![format!("{}", target)]);
+        match scripted_return("glCheckFramebufferStatus") {
+            Some(status) => status as GLenum,
+            None => FRAMEBUFFER_COMPLETE,
+        }
+    }
+
+    #[allow(non_snake_case)]
+    extern "C" fn glGetString(name: GLenum) -> *const GLubyte {
+        static MOCK: &'static [u8] = b"mock\0";
+        record("glGetString", vec![format!("{}", name)]);
+        MOCK.as_ptr()
+    }
+
+    pub fn lookup(name: &str) -> *const c_void {
+        let f: *const c_void = match name {
+            "glGenBuffers" => glGenBuffers as *const c_void,
+            "glGenFramebuffers" => glGenFramebuffers as *const c_void,
+            "glGenRenderbuffers" => glGenRenderbuffers as *const c_void,
+            "glGenTextures" => glGenTextures as *const c_void,
+            "glGenVertexArrays" => glGenVertexArrays as *const c_void,
+            "glDeleteBuffers" => glDeleteBuffers as *const c_void,
+            "glDeleteFramebuffers" => glDeleteFramebuffers as *const c_void,
+            "glDeleteRenderbuffers" => glDeleteRenderbuffers as *const c_void,
+            "glDeleteTextures" => glDeleteTextures as *const c_void,
+            "glDeleteVertexArrays" => glDeleteVertexArrays as *const c_void,
+            "glGetBufferParameteriv" => glGetBufferParameteriv as *const c_void,
+            "glGetProgramiv" => glGetProgramiv as *const c_void,
+            "glGetRenderbufferParameteriv" => glGetRenderbufferParameteriv as *const c_void,
+            "glGetShaderiv" => glGetShaderiv as *const c_void,
+            "glGetTexParameterfv" => glGetTexParameterfv as *const c_void,
+            "glGetTexParameteriv" => glGetTexParameteriv as *const c_void,
+            "glGetUniformfv" => glGetUniformfv as *const c_void,
+            "glGetUniformiv" => glGetUniformiv as *const c_void,
+            "glGetVertexAttribfv" => glGetVertexAttribfv as *const c_void,
+            "glGetVertexAttribiv" => glGetVertexAttribiv as *const c_void,
+            "glGetBooleanv" => glGetBooleanv as *const c_void,
+            "glGetFloatv" => glGetFloatv as *const c_void,
+            "glGetIntegerv" => glGetIntegerv as *const c_void,
+            "glGetFramebufferAttachmentParameteriv" => {
+                glGetFramebufferAttachmentParameteriv as *const c_void
+            }
+            "glGetAttachedShaders" => glGetAttachedShaders as *const c_void,
+            "glGetActiveAttrib" => glGetActiveAttrib as *const c_void,
+            "glGetActiveUniform" => glGetActiveUniform as *const c_void,
+            "glGetProgramInfoLog" => glGetProgramInfoLog as *const c_void,
+            "glGetShaderInfoLog" => glGetShaderInfoLog as *const c_void,
+            "glCreateProgram" => glCreateProgram as *const c_void,
+            "glCreateShader" => glCreateShader as *const c_void,
+            "glCheckFramebufferStatus" => glCheckFramebufferStatus as *const c_void,
+            "glGetString" => glGetString as *const c_void,
+            _ => ptr::null(),
+        };
+        f
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{calls, load, push_return, push_string, reset};
+    use gl2::{ActiveInfo, FLOAT_VEC4};
+
+    #[test]
+    fn records_calls_with_arguments() {
+        reset();
+        let gl = load();
+        gl.bind_texture(0x0DE1, 7);
+        let calls = calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "glBindTexture");
+        assert_eq!(calls[0].args, vec!["3553".to_string(), "7".to_string()]);
+    }
+
+    #[test]
+    fn generated_names_are_fresh() {
+        reset();
+        let gl = load();
+        assert_eq!(gl.gen_textures(2), vec![1, 2]);
+        assert_eq!(gl.gen_buffers(1), vec![3]);
+    }
+
+    #[test]
+    fn active_uniform_is_scripted() {
+        reset();
+        let gl = load();
+        push_return("glGetProgramiv", 16);
+        push_return("glGetActiveUniform", 3);
+        push_return("glGetActiveUniform", FLOAT_VEC4 as i64);
+        push_string("glGetActiveUniform", "colors[0]");
+        assert_eq!(gl.get_active_uniform(1, 0), ActiveInfo {
+            name: "colors[0]".to_string(),
+            size: 3,
+            ty: FLOAT_VEC4,
+        });
+    }
+
+    #[test]
+    fn strings_are_truncated_to_the_buffer() {
+        reset();
+        let gl = load();
+        push_return("glGetProgramiv", 4);
+        push_string("glGetActiveAttrib", "position");
+        assert_eq!(gl.get_active_attrib(1, 0).name.as_slice(), "pos");
+    }
+
+    #[test]
+    fn attached_shaders_fill_up_to_the_buffer() {
+        reset();
+        let gl = load();
+        push_return("glGetProgramiv", 2);
+        push_return("glGetAttachedShaders", 5);
+        push_return("glGetAttachedShaders", 6);
+        push_return("glGetAttachedShaders", 7);
+        assert_eq!(gl.get_attached_shaders(1), vec![5, 6]);
+    }
+}
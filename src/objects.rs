@@ -0,0 +1,210 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Owned GL objects that are deleted when they go out of scope.
+//!
+//! Each handle borrows the `Gl` context that created it. `leak` gives up ownership and returns
+//! the raw name, and `from_name` takes ownership of a name created elsewhere.
+
+use gl2::{Gl, GLenum, GLuint};
+use std::mem;
+
+macro_rules! gl_object {
+    ($(#[$attr:meta])* object $ty:ident; fn delete($gl:ident, $name:ident) $delete:block) => {
+        $(#[$attr])*
+        pub struct $ty<'a> {
+            gl: &'a Gl,
+            name: GLuint,
+        }
+
+        impl<'a> $ty<'a> {
+            /// Takes ownership of an existing object. It will be deleted when the returned
+            /// handle is dropped.
+            pub fn from_name(gl: &'a Gl, name: GLuint) -> $ty<'a> {
+                $ty {
+                    gl: gl,
+                    name: name,
+                }
+            }
+
+            /// Returns the raw name of the object.
+            pub fn name(&self) -> GLuint {
+                self.name
+            }
+
+            /// Gives up ownership of the object without deleting it, returning its raw name.
+            pub fn leak(self) -> GLuint {
+                let name = self.name;
+                unsafe {
+                    mem::forget(self);
+                }
+                name
+            }
+        }
+
+        #[unsafe_destructor]
+        impl<'a> Drop for $ty<'a> {
+            fn drop(&mut self) {
+                let $gl = self.gl;
+                let $name = self.name;
+                $delete
+            }
+        }
+    }
+}
+
+gl_object! {
+    /// A buffer object.
+    object Buffer;
+    fn delete(gl, name) { gl.delete_buffers(&[name]) }
+}
+
+impl<'a> Buffer<'a> {
+    pub fn new(gl: &'a Gl) -> Buffer<'a> {
+        Buffer::from_name(gl, gl.gen_buffers(1)[0])
+    }
+}
+
+gl_object! {
+    /// A texture object.
+    object Texture;
+    fn delete(gl, name) { gl.delete_textures(&[name]) }
+}
+
+impl<'a> Texture<'a> {
+    pub fn new(gl: &'a Gl) -> Texture<'a> {
+        Texture::from_name(gl, gl.gen_textures(1)[0])
+    }
+}
+
+gl_object! {
+    /// A framebuffer object.
+    object Framebuffer;
+    fn delete(gl, name) { gl.delete_frame_buffers(&[name]) }
+}
+
+impl<'a> Framebuffer<'a> {
+    pub fn new(gl: &'a Gl) -> Framebuffer<'a> {
+        Framebuffer::from_name(gl, gl.gen_framebuffers(1)[0])
+    }
+}
+
+gl_object! {
+    /// A renderbuffer object.
+    object Renderbuffer;
+    fn delete(gl, name) { gl.delete_render_buffers(&[name]) }
+}
+
+impl<'a> Renderbuffer<'a> {
+    pub fn new(gl: &'a Gl) -> Renderbuffer<'a> {
+        Renderbuffer::from_name(gl, gl.gen_renderbuffers(1)[0])
+    }
+}
+
+gl_object! {
+    /// A program object.
+    object Program;
+    fn delete(gl, name) { gl.delete_program(name) }
+}
+
+impl<'a> Program<'a> {
+    pub fn new(gl: &'a Gl) -> Program<'a> {
+        Program::from_name(gl, gl.create_program())
+    }
+}
+
+gl_object! {
+    /// A shader object.
+    object Shader;
+    fn delete(gl, name) { gl.delete_shader(name) }
+}
+
+impl<'a> Shader<'a> {
+    /// Creates a shader of the given type, `VERTEX_SHADER` or `FRAGMENT_SHADER`.
+    pub fn new(gl: &'a Gl, shader_type: GLenum) -> Shader<'a> {
+        Shader::from_name(gl, gl.create_shader(shader_type))
+    }
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+gl_object! {
+    /// A vertex array object.
+    object VertexArray;
+    fn delete(gl, name) { gl.delete_vertex_arrays(&[name]) }
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+impl<'a> VertexArray<'a> {
+    pub fn new(gl: &'a Gl) -> VertexArray<'a> {
+        VertexArray::from_name(gl, gl.gen_vertex_arrays(1)[0])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gl2::VERTEX_SHADER;
+    use mock;
+    use super::{Buffer, Framebuffer, Program, Renderbuffer, Shader, Texture};
+
+    fn deletions() -> Vec<mock::Call> {
+        mock::calls().into_iter().filter(|call| call.name.starts_with("glDelete")).collect()
+    }
+
+    fn call(name: &'static str, args: &[&str]) -> mock::Call {
+        mock::Call {
+            name: name,
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn dropping_deletes_the_object() {
+        mock::reset();
+        let gl = mock::load();
+        {
+            let buffer = Buffer::new(&gl);
+            let texture = Texture::new(&gl);
+            let framebuffer = Framebuffer::new(&gl);
+            let renderbuffer = Renderbuffer::new(&gl);
+            let program = Program::new(&gl);
+            let shader = Shader::new(&gl, VERTEX_SHADER);
+            assert_eq!((buffer.name(), texture.name(), framebuffer.name()), (1, 2, 3));
+            assert_eq!((renderbuffer.name(), program.name(), shader.name()), (4, 5, 6));
+            assert!(deletions().is_empty());
+        }
+        // Locals are dropped in reverse order of declaration.
+        assert_eq!(deletions(), vec![
+            call("glDeleteShader", &["6"]),
+            call("glDeleteProgram", &["5"]),
+            call("glDeleteRenderbuffers", &["1", "4"]),
+            call("glDeleteFramebuffers", &["1", "3"]),
+            call("glDeleteTextures", &["1", "2"]),
+            call("glDeleteBuffers", &["1", "1"]),
+        ]);
+    }
+
+    #[test]
+    fn from_name_takes_ownership() {
+        mock::reset();
+        let gl = mock::load();
+        drop(Texture::from_name(&gl, 42));
+        assert_eq!(deletions(), vec![call("glDeleteTextures", &["1", "42"])]);
+    }
+
+    #[test]
+    fn leak_returns_the_name_without_deleting() {
+        mock::reset();
+        let gl = mock::load();
+        let buffer = Buffer::new(&gl);
+        let program = Program::from_name(&gl, 9);
+        assert_eq!(buffer.leak(), 1);
+        assert_eq!(program.leak(), 9);
+        assert!(deletions().is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The sizes of pixel rectangles in client memory, as read and written by `read_pixels` and the
+//! texture upload calls, and the pixel-store state that lays them out.
+
+use error::PixelError;
+use gl2::{GLenum, GLint, GLsizei};
+use gl2::{ALPHA, BGRA, DEPTH_COMPONENT, LUMINANCE, LUMINANCE_ALPHA, RGB, RGBA};
+use gl2::{FLOAT, UNSIGNED_BYTE, UNSIGNED_INT, UNSIGNED_SHORT};
+use gl2::{UNSIGNED_INT_8_8_8_8_REV, UNSIGNED_SHORT_4_4_4_4, UNSIGNED_SHORT_5_5_5_1};
+use gl2::UNSIGNED_SHORT_5_6_5;
+use std::default::Default;
+use std::num::Int;
+
+/// The pixel-store parameters of a context that affect how rectangles are laid out in client
+/// memory, as returned by `Gl::pixel_store`.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct PixelStore {
+    /// `PACK_ALIGNMENT`, used by `read_pixels`.
+    pub pack_alignment: GLint,
+    /// `UNPACK_ALIGNMENT`, used by texture uploads.
+    pub unpack_alignment: GLint,
+    /// `UNPACK_ROW_LENGTH`, or 0 for rows as long as the rectangle. Not part of OpenGL ES 2.0.
+    pub unpack_row_length: GLint,
+    /// `UNPACK_CLIENT_STORAGE_APPLE`. Only meaningful on Mac OS X.
+    pub unpack_client_storage_apple: bool,
+}
+
+/// The pixel-store state of a new context.
+pub const INITIAL_PIXEL_STORE: PixelStore = PixelStore {
+    pack_alignment: 4,
+    unpack_alignment: 4,
+    unpack_row_length: 0,
+    unpack_client_storage_apple: false,
+};
+
+impl Default for PixelStore {
+    fn default() -> PixelStore {
+        INITIAL_PIXEL_STORE
+    }
+}
+
+impl PixelStore {
+    /// Returns the number of bytes `read_pixels` writes for a rectangle.
+    pub fn pack_size(&self, width: GLsizei, height: GLsizei, format: GLenum, ty: GLenum)
+                     -> Result<uint, PixelError> {
+        image_size(width, height, format, ty, self.pack_alignment as uint, 0)
+    }
+
+    /// Returns the number of bytes a texture upload reads for a rectangle.
+    pub fn unpack_size(&self, width: GLsizei, height: GLsizei, format: GLenum, ty: GLenum)
+                       -> Result<uint, PixelError> {
+        image_size(width, height, format, ty, self.unpack_alignment as uint,
+                   self.unpack_row_length as uint)
+    }
+}
+
+/// Returns the number of components in a pixel of `format`.
+pub fn components(format: GLenum) -> Result<uint, PixelError> {
+    match format {
+        ALPHA | LUMINANCE | DEPTH_COMPONENT => Ok(1),
+        LUMINANCE_ALPHA => Ok(2),
+        RGB => Ok(3),
+        RGBA | BGRA => Ok(4),
+        _ => Err(PixelError::UnsupportedFormat(format)),
+    }
+}
+
+/// Returns the size in bytes of one pixel of `format` stored as `ty`.
+pub fn bytes_per_pixel(format: GLenum, ty: GLenum) -> Result<uint, PixelError> {
+    let components = try!(components(format));
+    match (ty, format) {
+        (UNSIGNED_BYTE, _) => Ok(components),
+        (FLOAT, _) => Ok(components * 4),
+        (UNSIGNED_SHORT, DEPTH_COMPONENT) => Ok(2),
+        (UNSIGNED_INT, DEPTH_COMPONENT) => Ok(4),
+        (UNSIGNED_SHORT_5_6_5, RGB) => Ok(2),
+        (UNSIGNED_SHORT_4_4_4_4, RGBA) | (UNSIGNED_SHORT_5_5_5_1, RGBA) => Ok(2),
+        (UNSIGNED_INT_8_8_8_8_REV, RGBA) | (UNSIGNED_INT_8_8_8_8_REV, BGRA) => Ok(4),
+        _ => Err(PixelError::UnsupportedType(format, ty)),
+    }
+}
+
+/// Returns the number of bytes GL reads or writes for a `width` by `height` rectangle of `format`
+/// and `ty`. Rows are `row_length` pixels apart, or `width` if it is 0, and start on multiples of
+/// `alignment` bytes. The last row is not padded. Sizes that do not fit in a `uint` are an
+/// error rather than wrapping, which on 32-bit targets would let a short buffer through.
+pub fn image_size(width: GLsizei,
+                  height: GLsizei,
+                  format: GLenum,
+                  ty: GLenum,
+                  alignment: uint,
+                  row_length: uint)
+                  -> Result<uint, PixelError> {
+    if width < 0 || height < 0 {
+        return Err(PixelError::InvalidDimensions(width, height))
+    }
+    let bytes_per_pixel = try!(bytes_per_pixel(format, ty));
+    if width == 0 || height == 0 {
+        return Ok(0)
+    }
+    let row_length = if row_length == 0 { width as uint } else { row_length };
+    let size = row_length.checked_mul(bytes_per_pixel).and_then(|row| {
+        round_up(row, alignment)
+    }).and_then(|stride| stride.checked_mul(height as uint - 1)).and_then(|rows| {
+        (width as uint).checked_mul(bytes_per_pixel).and_then(|last_row| {
+            rows.checked_add(last_row)
+        })
+    });
+    match size {
+        Some(size) => Ok(size),
+        None => Err(PixelError::Overflow(width, height)),
+    }
+}
+
+/// Checks that a buffer of `actual` bytes can hold the `required` bytes of a rectangle.
+pub fn check_buffer_size(actual: uint, required: uint) -> Result<(), PixelError> {
+    if actual < required {
+        return Err(PixelError::BufferTooSmall {
+            required: required,
+            actual: actual,
+        })
+    }
+    Ok(())
+}
+
+fn round_up(size: uint, alignment: uint) -> Option<uint> {
+    if alignment <= 1 {
+        return Some(size)
+    }
+    size.checked_add(alignment - 1).map(|size| size / alignment * alignment)
+}
+
+#[cfg(test)]
+mod test {
+    use error::PixelError;
+    use gl2::{ALPHA, FLOAT, RGB, RGBA, UNSIGNED_BYTE, UNSIGNED_SHORT_5_6_5};
+    use std::{i32, uint};
+    use super::{bytes_per_pixel, image_size};
+
+    #[test]
+    fn tightly_packed_rows() {
+        assert_eq!(image_size(4, 3, RGBA, UNSIGNED_BYTE, 4, 0), Ok(48));
+    }
+
+    #[test]
+    fn rows_are_aligned_except_the_last() {
+        // 15-byte rows are padded to 16.
+        assert_eq!(image_size(5, 3, RGB, UNSIGNED_BYTE, 4, 0), Ok(16 * 2 + 15));
+        assert_eq!(image_size(5, 3, RGB, UNSIGNED_BYTE, 1, 0), Ok(15 * 3));
+    }
+
+    #[test]
+    fn row_length_sets_the_stride() {
+        assert_eq!(image_size(2, 3, ALPHA, UNSIGNED_BYTE, 1, 10), Ok(10 * 2 + 2));
+    }
+
+    #[test]
+    fn empty_and_negative_rectangles() {
+        assert_eq!(image_size(0, 7, RGBA, UNSIGNED_BYTE, 4, 0), Ok(0));
+        assert_eq!(image_size(-1, 7, RGBA, UNSIGNED_BYTE, 4, 0),
+                   Err(PixelError::InvalidDimensions(-1, 7)));
+    }
+
+    #[test]
+    fn sizes_that_do_not_fit_are_an_error() {
+        assert_eq!(image_size(i32::MAX, i32::MAX, RGBA, FLOAT, 4, 0),
+                   Err(PixelError::Overflow(i32::MAX, i32::MAX)));
+        assert_eq!(image_size(1, 2, RGBA, UNSIGNED_BYTE, 4, uint::MAX / 2),
+                   Err(PixelError::Overflow(1, 2)));
+    }
+
+    #[test]
+    fn packed_types_need_a_matching_format() {
+        assert_eq!(bytes_per_pixel(RGB, UNSIGNED_SHORT_5_6_5), Ok(2));
+        assert_eq!(bytes_per_pixel(RGBA, UNSIGNED_SHORT_5_6_5),
+                   Err(PixelError::UnsupportedType(RGBA, UNSIGNED_SHORT_5_6_5)));
+    }
+}
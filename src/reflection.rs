@@ -0,0 +1,119 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Enumeration of the uniforms and attributes a linked program expects.
+
+use gl2::{ActiveInfo, Gl, GLint, GLuint, ACTIVE_ATTRIBUTES, ACTIVE_UNIFORMS};
+
+/// An active uniform or attribute together with its location.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct ActiveVariable {
+    pub info: ActiveInfo,
+    /// The location, or -1 for built-in variables such as `gl_FragCoord`.
+    pub location: GLint,
+}
+
+/// Every active uniform and attribute of a linked program.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct ProgramReflection {
+    pub uniforms: Vec<ActiveVariable>,
+    pub attributes: Vec<ActiveVariable>,
+}
+
+impl ProgramReflection {
+    /// Queries the active uniforms and attributes of `program`, which must be linked.
+    pub fn new(gl: &Gl, program: GLuint) -> ProgramReflection {
+        let uniform_count = gl.get_program_iv(program, ACTIVE_UNIFORMS) as GLuint;
+        let uniforms = range(0, uniform_count).map(|index| {
+            let info = gl.get_active_uniform(program, index);
+            let location = gl.get_uniform_location(program, info.name.as_slice());
+            ActiveVariable {
+                info: info,
+                location: location,
+            }
+        }).collect();
+
+        let attribute_count = gl.get_program_iv(program, ACTIVE_ATTRIBUTES) as GLuint;
+        let attributes = range(0, attribute_count).map(|index| {
+            let info = gl.get_active_attrib(program, index);
+            let location = gl.get_attrib_location(program, info.name.as_slice());
+            ActiveVariable {
+                info: info,
+                location: location,
+            }
+        }).collect();
+
+        ProgramReflection {
+            uniforms: uniforms,
+            attributes: attributes,
+        }
+    }
+
+    /// Looks up a uniform by name. Arrays can be found with or without their `[0]` suffix.
+    pub fn uniform(&self, name: &str) -> Option<&ActiveVariable> {
+        find(self.uniforms.as_slice(), name)
+    }
+
+    /// Looks up an attribute by name.
+    pub fn attribute(&self, name: &str) -> Option<&ActiveVariable> {
+        find(self.attributes.as_slice(), name)
+    }
+}
+
+fn find<'a>(variables: &'a [ActiveVariable], name: &str) -> Option<&'a ActiveVariable> {
+    variables.iter().find(|variable| {
+        let full_name = variable.info.name.as_slice();
+        full_name == name || (full_name.ends_with("[0]") &&
+                              full_name.slice_to(full_name.len() - 3) == name)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use gl2::{FLOAT_MAT4, FLOAT_VEC3, FLOAT_VEC4};
+    use mock;
+    use super::ProgramReflection;
+
+    #[test]
+    fn finds_uniforms_and_attributes() {
+        mock::reset();
+        let gl = mock::load();
+        // ACTIVE_UNIFORMS, then ACTIVE_UNIFORM_MAX_LENGTH for each uniform.
+        mock::push_return("glGetProgramiv", 2);
+        mock::push_return("glGetProgramiv", 32);
+        mock::push_return("glGetProgramiv", 32);
+        // ACTIVE_ATTRIBUTES, then ACTIVE_ATTRIBUTE_MAX_LENGTH for each attribute.
+        mock::push_return("glGetProgramiv", 1);
+        mock::push_return("glGetProgramiv", 32);
+
+        mock::push_return("glGetActiveUniform", 1);
+        mock::push_return("glGetActiveUniform", FLOAT_MAT4 as i64);
+        mock::push_string("glGetActiveUniform", "transform");
+        mock::push_return("glGetActiveUniform", 4);
+        mock::push_return("glGetActiveUniform", FLOAT_VEC4 as i64);
+        mock::push_string("glGetActiveUniform", "colors[0]");
+        mock::push_return("glGetUniformLocation", 0);
+        mock::push_return("glGetUniformLocation", 1);
+
+        mock::push_return("glGetActiveAttrib", 1);
+        mock::push_return("glGetActiveAttrib", FLOAT_VEC3 as i64);
+        mock::push_string("glGetActiveAttrib", "position");
+        mock::push_return("glGetAttribLocation", 2);
+
+        let reflection = ProgramReflection::new(&gl, 1);
+        assert_eq!(reflection.uniforms.len(), 2);
+        assert_eq!(reflection.uniform("transform").unwrap().info.ty, FLOAT_MAT4);
+        let colors = reflection.uniform("colors").unwrap();
+        assert_eq!(colors.info.size, 4);
+        assert_eq!(colors.location, 1);
+        assert!(reflection.uniform("colors[0]").is_some());
+        assert_eq!(reflection.attribute("position").unwrap().location, 2);
+        assert!(reflection.attribute("normal").is_none());
+    }
+}
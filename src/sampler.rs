@@ -0,0 +1,140 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The filtering and wrapping parameters of a texture.
+
+use gl2;
+use gl2::{Gl, GLenum, GLint};
+use std::default::Default;
+
+/// How a texture is sampled. OpenGL ES 2.0 has no sampler objects, so this state lives in the
+/// texture itself.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct SamplerState {
+    /// The minifying filter, e.g. `LINEAR_MIPMAP_LINEAR`.
+    pub min_filter: GLenum,
+    /// The magnifying filter, `NEAREST` or `LINEAR`.
+    pub mag_filter: GLenum,
+    /// The wrap mode along S, e.g. `CLAMP_TO_EDGE`.
+    pub wrap_s: GLenum,
+    /// The wrap mode along T.
+    pub wrap_t: GLenum,
+}
+
+impl Default for SamplerState {
+    /// The initial state of a texture.
+    fn default() -> SamplerState {
+        SamplerState {
+            min_filter: gl2::NEAREST_MIPMAP_LINEAR,
+            mag_filter: gl2::LINEAR,
+            wrap_s: gl2::REPEAT,
+            wrap_t: gl2::REPEAT,
+        }
+    }
+}
+
+impl SamplerState {
+    /// Applies this state to the texture bound to `target`, e.g. `TEXTURE_2D`.
+    pub fn apply(&self, gl: &Gl, target: GLenum) {
+        gl.tex_parameter_i(target, gl2::TEXTURE_MIN_FILTER, self.min_filter as GLint);
+        gl.tex_parameter_i(target, gl2::TEXTURE_MAG_FILTER, self.mag_filter as GLint);
+        gl.tex_parameter_i(target, gl2::TEXTURE_WRAP_S, self.wrap_s as GLint);
+        gl.tex_parameter_i(target, gl2::TEXTURE_WRAP_T, self.wrap_t as GLint);
+    }
+
+    /// Reads the state of the texture bound to `target`.
+    pub fn get(gl: &Gl, target: GLenum) -> SamplerState {
+        SamplerState {
+            min_filter: gl.get_tex_parameter_iv(target, gl2::TEXTURE_MIN_FILTER) as GLenum,
+            mag_filter: gl.get_tex_parameter_iv(target, gl2::TEXTURE_MAG_FILTER) as GLenum,
+            wrap_s: gl.get_tex_parameter_iv(target, gl2::TEXTURE_WRAP_S) as GLenum,
+            wrap_t: gl.get_tex_parameter_iv(target, gl2::TEXTURE_WRAP_T) as GLenum,
+        }
+    }
+
+    /// Whether the minifying filter reads mipmaps, which must then be complete.
+    pub fn uses_mipmaps(&self) -> bool {
+        match self.min_filter {
+            gl2::NEAREST | gl2::LINEAR => false,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gl2::{CLAMP_TO_EDGE, GLenum, LINEAR, LINEAR_MIPMAP_LINEAR, LINEAR_MIPMAP_NEAREST};
+    use gl2::{MIRRORED_REPEAT, NEAREST, NEAREST_MIPMAP_LINEAR, NEAREST_MIPMAP_NEAREST, TEXTURE_2D};
+    use gl2::{TEXTURE_MAG_FILTER, TEXTURE_MIN_FILTER, TEXTURE_WRAP_S, TEXTURE_WRAP_T};
+    use mock;
+    use std::default::Default;
+    use super::SamplerState;
+
+    fn state() -> SamplerState {
+        SamplerState {
+            min_filter: LINEAR_MIPMAP_NEAREST,
+            mag_filter: NEAREST,
+            wrap_s: CLAMP_TO_EDGE,
+            wrap_t: MIRRORED_REPEAT,
+        }
+    }
+
+    // The arguments of a `glTexParameteri` call on `TEXTURE_2D`.
+    fn tex_parameter(pname: GLenum, value: GLenum) -> Vec<String> {
+        vec![format!("{}", TEXTURE_2D), format!("{}", pname), format!("{}", value)]
+    }
+
+    #[test]
+    fn apply_sets_every_parameter() {
+        mock::reset();
+        let gl = mock::load();
+        state().apply(&gl, TEXTURE_2D);
+        let args: Vec<Vec<String>> = mock::calls().into_iter()
+                                                  .filter(|call| call.name == "glTexParameteri")
+                                                  .map(|call| call.args)
+                                                  .collect();
+        assert_eq!(args, vec![tex_parameter(TEXTURE_MIN_FILTER, LINEAR_MIPMAP_NEAREST),
+                              tex_parameter(TEXTURE_MAG_FILTER, NEAREST),
+                              tex_parameter(TEXTURE_WRAP_S, CLAMP_TO_EDGE),
+                              tex_parameter(TEXTURE_WRAP_T, MIRRORED_REPEAT)]);
+    }
+
+    #[test]
+    fn get_reads_every_parameter() {
+        mock::reset();
+        let gl = mock::load();
+        let state = state();
+        mock::push_return("glGetTexParameteriv", state.min_filter as i64);
+        mock::push_return("glGetTexParameteriv", state.mag_filter as i64);
+        mock::push_return("glGetTexParameteriv", state.wrap_s as i64);
+        mock::push_return("glGetTexParameteriv", state.wrap_t as i64);
+        assert_eq!(SamplerState::get(&gl, TEXTURE_2D), state);
+        let pnames: Vec<String> = mock::calls().iter().map(|call| call.args[1].clone()).collect();
+        assert_eq!(pnames, vec![format!("{}", TEXTURE_MIN_FILTER),
+                                format!("{}", TEXTURE_MAG_FILTER),
+                                format!("{}", TEXTURE_WRAP_S),
+                                format!("{}", TEXTURE_WRAP_T)]);
+    }
+
+    #[test]
+    fn only_mipmap_filters_use_mipmaps() {
+        let uses_mipmaps = |min_filter: GLenum| {
+            SamplerState {
+                min_filter: min_filter,
+                ..Default::default()
+            }.uses_mipmaps()
+        };
+        assert!(!uses_mipmaps(NEAREST));
+        assert!(!uses_mipmaps(LINEAR));
+        assert!(uses_mipmaps(NEAREST_MIPMAP_NEAREST));
+        assert!(uses_mipmaps(LINEAR_MIPMAP_NEAREST));
+        assert!(uses_mipmaps(NEAREST_MIPMAP_LINEAR));
+        assert!(uses_mipmaps(LINEAR_MIPMAP_LINEAR));
+    }
+}
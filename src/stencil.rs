@@ -0,0 +1,197 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The complete stencil state of a context, for both faces.
+
+use gl2;
+use gl2::{Gl, GLenum, GLint, GLuint};
+use std::default::Default;
+
+/// The stencil test and operations for one face.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct StencilFaceState {
+    /// The comparison function, e.g. `EQUAL`.
+    pub func: GLenum,
+    /// The reference value compared against the stencil buffer.
+    pub reference: GLint,
+    /// The mask ANDed with both the reference and the stored value before comparing.
+    pub value_mask: GLuint,
+    /// The operation when the stencil test fails.
+    pub fail: GLenum,
+    /// The operation when the stencil test passes and the depth test fails.
+    pub depth_fail: GLenum,
+    /// The operation when both tests pass.
+    pub depth_pass: GLenum,
+    /// The mask of stencil bits that may be written.
+    pub write_mask: GLuint,
+}
+
+impl Default for StencilFaceState {
+    /// The initial state of a context.
+    fn default() -> StencilFaceState {
+        StencilFaceState {
+            func: gl2::ALWAYS,
+            reference: 0,
+            value_mask: !0,
+            fail: gl2::KEEP,
+            depth_fail: gl2::KEEP,
+            depth_pass: gl2::KEEP,
+            write_mask: !0,
+        }
+    }
+}
+
+impl StencilFaceState {
+    /// Applies this state to `face`, which is `FRONT`, `BACK` or `FRONT_AND_BACK`.
+    pub fn apply(&self, gl: &Gl, face: GLenum) {
+        gl.stencil_func_separate(face, self.func, self.reference, self.value_mask);
+        gl.stencil_op_separate(face, self.fail, self.depth_fail, self.depth_pass);
+        gl.stencil_mask_separate(face, self.write_mask);
+    }
+}
+
+/// The stencil state of both faces.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct StencilState {
+    pub front: StencilFaceState,
+    pub back: StencilFaceState,
+}
+
+impl Default for StencilState {
+    fn default() -> StencilState {
+        StencilState::both(Default::default())
+    }
+}
+
+impl StencilState {
+    /// Uses the same state for front and back faces.
+    pub fn both(face: StencilFaceState) -> StencilState {
+        StencilState {
+            front: face.clone(),
+            back: face,
+        }
+    }
+
+    /// Applies the state of both faces, with a single set of calls when they match.
+    pub fn apply(&self, gl: &Gl) {
+        if self.front == self.back {
+            self.front.apply(gl, gl2::FRONT_AND_BACK);
+        } else {
+            self.front.apply(gl, gl2::FRONT);
+            self.back.apply(gl, gl2::BACK);
+        }
+    }
+
+    /// Reads the current stencil state of `gl`.
+    pub fn get(gl: &Gl) -> StencilState {
+        StencilState {
+            front: StencilFaceState {
+                func: get_integer(gl, gl2::STENCIL_FUNC) as GLenum,
+                reference: get_integer(gl, gl2::STENCIL_REF),
+                value_mask: get_integer(gl, gl2::STENCIL_VALUE_MASK) as GLuint,
+                fail: get_integer(gl, gl2::STENCIL_FAIL) as GLenum,
+                depth_fail: get_integer(gl, gl2::STENCIL_PASS_DEPTH_FAIL) as GLenum,
+                depth_pass: get_integer(gl, gl2::STENCIL_PASS_DEPTH_PASS) as GLenum,
+                write_mask: get_integer(gl, gl2::STENCIL_WRITEMASK) as GLuint,
+            },
+            back: StencilFaceState {
+                func: get_integer(gl, gl2::STENCIL_BACK_FUNC) as GLenum,
+                reference: get_integer(gl, gl2::STENCIL_BACK_REF),
+                value_mask: get_integer(gl, gl2::STENCIL_BACK_VALUE_MASK) as GLuint,
+                fail: get_integer(gl, gl2::STENCIL_BACK_FAIL) as GLenum,
+                depth_fail: get_integer(gl, gl2::STENCIL_BACK_PASS_DEPTH_FAIL) as GLenum,
+                depth_pass: get_integer(gl, gl2::STENCIL_BACK_PASS_DEPTH_PASS) as GLenum,
+                write_mask: get_integer(gl, gl2::STENCIL_BACK_WRITEMASK) as GLuint,
+            },
+        }
+    }
+}
+
+fn get_integer(gl: &Gl, pname: GLenum) -> GLint {
+    let mut result = [0];
+    gl.get_integer_v(pname, result.as_mut_slice());
+    result[0]
+}
+
+#[cfg(test)]
+mod test {
+    use gl2::{BACK, DECR, EQUAL, FRONT, FRONT_AND_BACK, INCR, INVERT, KEEP, LESS, REPLACE};
+    use gl2::ZERO;
+    use mock;
+    use std::default::Default;
+    use super::{StencilFaceState, StencilState};
+
+    fn faces() -> Vec<String> {
+        mock::calls().iter().map(|call| call.args[0].clone()).collect()
+    }
+
+    #[test]
+    fn matching_faces_are_applied_together() {
+        mock::reset();
+        let gl = mock::load();
+        let state: StencilState = Default::default();
+        state.apply(&gl);
+        let names: Vec<&str> = mock::calls().iter().map(|call| call.name).collect();
+        assert_eq!(names, vec!["glStencilFuncSeparate", "glStencilOpSeparate",
+                               "glStencilMaskSeparate"]);
+        assert!(faces().iter().all(|face| *face == format!("{}", FRONT_AND_BACK)));
+    }
+
+    #[test]
+    fn different_faces_are_applied_separately() {
+        mock::reset();
+        let gl = mock::load();
+        let mut state: StencilState = Default::default();
+        state.back.depth_pass = INCR;
+        state.apply(&gl);
+        let front = format!("{}", FRONT);
+        let back = format!("{}", BACK);
+        assert_eq!(faces(), vec![front.clone(), front.clone(), front, back.clone(), back.clone(),
+                                 back]);
+        let calls = mock::calls();
+        assert_eq!(calls[1].args[3], format!("{}", KEEP));
+        assert_eq!(calls[4].args[3], format!("{}", INCR));
+    }
+
+    #[test]
+    fn get_reads_both_faces() {
+        mock::reset();
+        let gl = mock::load();
+        let state = StencilState {
+            front: StencilFaceState {
+                func: EQUAL,
+                reference: 1,
+                value_mask: 0xff,
+                fail: ZERO,
+                depth_fail: REPLACE,
+                depth_pass: INCR,
+                write_mask: !0,
+            },
+            back: StencilFaceState {
+                func: LESS,
+                reference: 2,
+                value_mask: 0x0f,
+                fail: INVERT,
+                depth_fail: DECR,
+                depth_pass: KEEP,
+                write_mask: 0,
+            },
+        };
+        for face in [&state.front, &state.back].iter() {
+            mock::push_return("glGetIntegerv", face.func as i64);
+            mock::push_return("glGetIntegerv", face.reference as i64);
+            mock::push_return("glGetIntegerv", face.value_mask as i64);
+            mock::push_return("glGetIntegerv", face.fail as i64);
+            mock::push_return("glGetIntegerv", face.depth_fail as i64);
+            mock::push_return("glGetIntegerv", face.depth_pass as i64);
+            mock::push_return("glGetIntegerv", face.write_mask as i64);
+        }
+        assert_eq!(StencilState::get(&gl), state);
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The `gl2` wrappers that take enumerated arguments, accepting the typed enums from `enums`
+//! instead of bare `GLenum`s.
+
+use enums::{BeginMode, BlendEquation, BlendingFactor, BufferTarget, BufferUsage, Capability};
+use enums::{CullFaceMode, ElementType, FramebufferStatus, FramebufferTarget};
+use enums::{FrontFaceDirection, HintMode, HintTarget};
+use enums::{PixelFormat, PixelType, RenderbufferInternalFormat, RenderbufferTarget, ShaderType};
+use enums::{StencilFunction, StencilOp, StringName, TextureParameterName, TextureTarget};
+use error::{IndexCountError, PixelError};
+use gl2::{Gl, GLenum, GLfloat, GLint, GLsizei, GLuint, IndexType};
+
+pub fn bind_buffer(gl: &Gl, target: BufferTarget, buffer: GLuint) {
+    gl.bind_buffer(target.to_gl(), buffer)
+}
+
+pub fn bind_framebuffer(gl: &Gl, target: FramebufferTarget, framebuffer: GLuint) {
+    gl.bind_framebuffer(target.to_gl(), framebuffer)
+}
+
+pub fn bind_renderbuffer(gl: &Gl, target: RenderbufferTarget, renderbuffer: GLuint) {
+    gl.bind_renderbuffer(target.to_gl(), renderbuffer)
+}
+
+pub fn bind_texture(gl: &Gl, target: TextureTarget, texture: GLuint) {
+    gl.bind_texture(target.to_gl(), texture)
+}
+
+pub fn blend_equation(gl: &Gl, mode: BlendEquation) {
+    gl.blend_equation(mode.to_gl())
+}
+
+pub fn blend_equation_separate(gl: &Gl, mode_rgb: BlendEquation, mode_alpha: BlendEquation) {
+    gl.blend_equation_separate(mode_rgb.to_gl(), mode_alpha.to_gl())
+}
+
+pub fn blend_func(gl: &Gl, sfactor: BlendingFactor, dfactor: BlendingFactor) {
+    gl.blend_func(sfactor.to_gl(), dfactor.to_gl())
+}
+
+pub fn blend_func_separate(gl: &Gl,
+                           src_rgb: BlendingFactor,
+                           dst_rgb: BlendingFactor,
+                           src_alpha: BlendingFactor,
+                           dst_alpha: BlendingFactor) {
+    gl.blend_func_separate(src_rgb.to_gl(), dst_rgb.to_gl(), src_alpha.to_gl(), dst_alpha.to_gl())
+}
+
+#[cfg(not(any(target_os="android", target_os="macos", mac_10_6)))]
+pub fn buffer_data<T>(gl: &Gl, target: BufferTarget, data: &[T], usage: BufferUsage) {
+    gl.buffer_data(target.to_gl(), data, usage.to_gl())
+}
+
+#[cfg(target_os="android")]
+pub fn buffer_data(gl: &Gl, target: BufferTarget, data: &[f32], usage: BufferUsage) {
+    gl.buffer_data(target.to_gl(), data, usage.to_gl())
+}
+
+pub fn buffer_sub_data<T>(gl: &Gl, target: BufferTarget, element_offset_index: uint, data: &[T]) {
+    gl.buffer_sub_data(target.to_gl(), element_offset_index, data)
+}
+
+/// Returns the completeness of the framebuffer bound to `target`, or the raw status if it is not
+/// one OpenGL ES 2.0 defines, such as 0 when the check itself fails.
+pub fn check_framebuffer_status(gl: &Gl, target: FramebufferTarget)
+                                -> Result<FramebufferStatus, GLenum> {
+    let status = gl.check_framebuffer_status(target.to_gl());
+    match FramebufferStatus::from_gl(status) {
+        Some(status) => Ok(status),
+        None => Err(status),
+    }
+}
+
+pub fn create_shader(gl: &Gl, shader_type: ShaderType) -> GLuint {
+    gl.create_shader(shader_type.to_gl())
+}
+
+pub fn cull_face(gl: &Gl, mode: CullFaceMode) {
+    gl.cull_face(mode.to_gl())
+}
+
+pub fn depth_func(gl: &Gl, func: StencilFunction) {
+    gl.depth_func(func.to_gl())
+}
+
+pub fn draw_arrays(gl: &Gl, mode: BeginMode, first: GLint, count: GLsizei) {
+    gl.draw_arrays(mode.to_gl(), first, count)
+}
+
+pub fn draw_elements(gl: &Gl,
+                     mode: BeginMode,
+                     count: GLsizei,
+                     element_type: ElementType,
+                     indices: Option<&[u8]>) {
+    gl.draw_elements(mode.to_gl(), count, element_type.to_gl(), indices)
+}
+
+pub fn draw_elements_indexed<T: IndexType>(gl: &Gl, mode: BeginMode, count: GLsizei, indices: &[T])
+                                           -> Result<(), IndexCountError> {
+    gl.draw_elements_indexed(mode.to_gl(), count, indices)
+}
+
+pub fn draw_elements_offset<T: IndexType>(gl: &Gl, mode: BeginMode, count: GLsizei, offset: uint)
+                                          -> Result<(), IndexCountError> {
+    gl.draw_elements_offset::<T>(mode.to_gl(), count, offset)
+}
+
+pub fn enable(gl: &Gl, cap: Capability) {
+    gl.enable(cap.to_gl())
+}
+
+pub fn disable(gl: &Gl, cap: Capability) {
+    gl.disable(cap.to_gl())
+}
+
+pub fn is_enabled(gl: &Gl, cap: Capability) -> bool {
+    gl.is_enabled(cap.to_gl())
+}
+
+pub fn front_face(gl: &Gl, mode: FrontFaceDirection) {
+    gl.front_face(mode.to_gl())
+}
+
+pub fn generate_mipmap(gl: &Gl, target: TextureTarget) {
+    gl.generate_mipmap(target.to_gl())
+}
+
+pub fn get_string(gl: &Gl, which: StringName) -> String {
+    gl.get_string(which.to_gl())
+}
+
+pub fn hint(gl: &Gl, target: HintTarget, mode: HintMode) {
+    gl.hint(target.to_gl(), mode.to_gl())
+}
+
+pub fn read_pixels(gl: &Gl,
+                   x: GLint,
+                   y: GLint,
+                   width: GLsizei,
+                   height: GLsizei,
+                   format: PixelFormat,
+                   pixel_type: PixelType) -> Result<Vec<u8>, PixelError> {
+    gl.read_pixels(x, y, width, height, format.to_gl(), pixel_type.to_gl())
+}
+
+pub fn read_pixels_into(gl: &Gl,
+                        x: GLint,
+                        y: GLint,
+                        width: GLsizei,
+                        height: GLsizei,
+                        format: PixelFormat,
+                        pixel_type: PixelType,
+                        pixels: &mut [u8]) -> Result<(), PixelError> {
+    gl.read_pixels_into(x, y, width, height, format.to_gl(), pixel_type.to_gl(), pixels)
+}
+
+pub fn renderbuffer_storage(gl: &Gl,
+                            target: RenderbufferTarget,
+                            internalformat: RenderbufferInternalFormat,
+                            width: GLsizei,
+                            height: GLsizei) {
+    gl.renderbuffer_storage(target.to_gl(), internalformat.to_gl(), width, height)
+}
+
+pub fn stencil_func(gl: &Gl, func: StencilFunction, reference: GLint, mask: GLuint) {
+    gl.stencil_func(func.to_gl(), reference, mask)
+}
+
+pub fn stencil_op(gl: &Gl, sfail: StencilOp, dpfail: StencilOp, dppass: StencilOp) {
+    gl.stencil_op(sfail.to_gl(), dpfail.to_gl(), dppass.to_gl())
+}
+
+pub fn tex_image_2d(gl: &Gl,
+                    target: TextureTarget,
+                    level: GLint,
+                    internal_format: PixelFormat,
+                    width: GLsizei,
+                    height: GLsizei,
+                    border: GLint,
+                    format: PixelFormat,
+                    ty: PixelType,
+                    opt_data: Option<&[u8]>) -> Result<(), PixelError> {
+    gl.tex_image_2d(target.to_gl(), level, internal_format.to_gl() as GLint, width, height,
+                    border, format.to_gl(), ty.to_gl(), opt_data)
+}
+
+pub fn tex_sub_image_2d(gl: &Gl,
+                        target: TextureTarget,
+                        level: GLint,
+                        xoffset: GLint,
+                        yoffset: GLint,
+                        width: GLsizei,
+                        height: GLsizei,
+                        format: PixelFormat,
+                        ty: PixelType,
+                        opt_data: Option<&[u8]>) -> Result<(), PixelError> {
+    gl.tex_sub_image_2d(target.to_gl(), level, xoffset, yoffset, width, height, format.to_gl(),
+                        ty.to_gl(), opt_data)
+}
+
+pub fn tex_parameter_f(gl: &Gl, target: TextureTarget, pname: TextureParameterName, param: GLfloat) {
+    gl.tex_parameter_f(target.to_gl(), pname.to_gl(), param)
+}
+
+pub fn tex_parameter_i(gl: &Gl, target: TextureTarget, pname: TextureParameterName, param: GLint) {
+    gl.tex_parameter_i(target.to_gl(), pname.to_gl(), param)
+}
+
+pub fn get_tex_parameter_iv(gl: &Gl, target: TextureTarget, pname: TextureParameterName) -> GLint {
+    gl.get_tex_parameter_iv(target.to_gl(), pname.to_gl())
+}
@@ -0,0 +1,168 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Typed readback of uniform values, sized from the type the program reports for the uniform.
+
+use gl2::{Gl, GLenum, GLfloat, GLint, GLuint, ACTIVE_UNIFORMS};
+use gl2::{BOOL, BOOL_VEC2, BOOL_VEC3, BOOL_VEC4, FLOAT, FLOAT_MAT2, FLOAT_MAT3, FLOAT_MAT4};
+use gl2::{FLOAT_VEC2, FLOAT_VEC3, FLOAT_VEC4, INT, INT_VEC2, INT_VEC3, INT_VEC4};
+use gl2::{SAMPLER_2D, SAMPLER_CUBE};
+
+/// The value of a uniform. Matrices are in column-major order.
+#[deriving(Clone, PartialEq, Show)]
+pub enum UniformValue {
+    Float(f32),
+    FloatVec2([f32, ..2]),
+    FloatVec3([f32, ..3]),
+    FloatVec4([f32, ..4]),
+    FloatMat2([f32, ..4]),
+    FloatMat3([f32, ..9]),
+    FloatMat4([f32, ..16]),
+    Int(i32),
+    IntVec2([i32, ..2]),
+    IntVec3([i32, ..3]),
+    IntVec4([i32, ..4]),
+    Bool(bool),
+    BoolVec2([bool, ..2]),
+    BoolVec3([bool, ..3]),
+    BoolVec4([bool, ..4]),
+    /// The texture unit a `sampler2D` reads from.
+    Sampler2D(i32),
+    /// The texture unit a `samplerCube` reads from.
+    SamplerCube(i32),
+}
+
+/// Reads the uniform `name` of the linked `program`. Elements of arrays can be named as in
+/// GLSL, e.g. `lights[2]`. Returns `None` if there is no such active uniform.
+pub fn get_uniform(gl: &Gl, program: GLuint, name: &str) -> Option<UniformValue> {
+    let location = gl.get_uniform_location(program, name);
+    if location == -1 {
+        return None
+    }
+    match uniform_type(gl, program, name) {
+        Some(ty) => get_uniform_value(gl, program, location, ty),
+        None => None,
+    }
+}
+
+/// Reads the uniform at `location`, whose type as reported by `get_active_uniform` is `ty`.
+/// Returns `None` for types outside OpenGL ES 2.0.
+pub fn get_uniform_value(gl: &Gl, program: GLuint, location: GLint, ty: GLenum)
+                         -> Option<UniformValue> {
+    let mut f = [0.0 as GLfloat, ..16];
+    let mut i = [0 as GLint, ..4];
+    match ty {
+        FLOAT | FLOAT_VEC2 | FLOAT_VEC3 | FLOAT_VEC4 | FLOAT_MAT2 | FLOAT_MAT3 | FLOAT_MAT4 => {
+            gl.get_uniform_fv(program, location, f.as_mut_slice())
+        }
+        INT | INT_VEC2 | INT_VEC3 | INT_VEC4 | BOOL | BOOL_VEC2 | BOOL_VEC3 | BOOL_VEC4 |
+        SAMPLER_2D | SAMPLER_CUBE => gl.get_uniform_iv(program, location, i.as_mut_slice()),
+        _ => return None,
+    }
+
+    Some(match ty {
+        FLOAT => UniformValue::Float(f[0]),
+        FLOAT_VEC2 => UniformValue::FloatVec2([f[0], f[1]]),
+        FLOAT_VEC3 => UniformValue::FloatVec3([f[0], f[1], f[2]]),
+        FLOAT_VEC4 => UniformValue::FloatVec4([f[0], f[1], f[2], f[3]]),
+        FLOAT_MAT2 => UniformValue::FloatMat2([f[0], f[1], f[2], f[3]]),
+        FLOAT_MAT3 => {
+            let mut m = [0.0, ..9];
+            for (dst, src) in m.iter_mut().zip(f.iter()) {
+                *dst = *src;
+            }
+            UniformValue::FloatMat3(m)
+        }
+        FLOAT_MAT4 => UniformValue::FloatMat4(f),
+        INT => UniformValue::Int(i[0]),
+        INT_VEC2 => UniformValue::IntVec2([i[0], i[1]]),
+        INT_VEC3 => UniformValue::IntVec3([i[0], i[1], i[2]]),
+        INT_VEC4 => UniformValue::IntVec4([i[0], i[1], i[2], i[3]]),
+        BOOL => UniformValue::Bool(i[0] != 0),
+        BOOL_VEC2 => UniformValue::BoolVec2([i[0] != 0, i[1] != 0]),
+        BOOL_VEC3 => UniformValue::BoolVec3([i[0] != 0, i[1] != 0, i[2] != 0]),
+        BOOL_VEC4 => UniformValue::BoolVec4([i[0] != 0, i[1] != 0, i[2] != 0, i[3] != 0]),
+        SAMPLER_2D => UniformValue::Sampler2D(i[0]),
+        SAMPLER_CUBE => UniformValue::SamplerCube(i[0]),
+        _ => unreachable!(),
+    })
+}
+
+// Finds the type of the active uniform `name`, ignoring the subscript of an array element.
+fn uniform_type(gl: &Gl, program: GLuint, name: &str) -> Option<GLenum> {
+    let name = strip_subscript(name);
+    let count = gl.get_program_iv(program, ACTIVE_UNIFORMS) as GLuint;
+    for index in range(0, count) {
+        let info = gl.get_active_uniform(program, index);
+        if strip_first_element(info.name.as_slice()) == name {
+            return Some(info.ty)
+        }
+    }
+    None
+}
+
+// Strips a final array subscript, so that `lights[2]` and `a[1].b[3]` name the arrays `lights`
+// and `a[1].b`. Subscripts before a `.` select a struct and are part of the uniform's name.
+fn strip_subscript(name: &str) -> &str {
+    if !name.ends_with("]") {
+        return name
+    }
+    match name.rfind('[') {
+        Some(index) => {
+            let subscript = name.slice(index + 1, name.len() - 1);
+            if from_str::<uint>(subscript).is_some() {
+                name.slice_to(index)
+            } else {
+                name
+            }
+        }
+        None => name,
+    }
+}
+
+// Strips the `[0]` that `get_active_uniform` appends to the names of arrays.
+fn strip_first_element(name: &str) -> &str {
+    if name.ends_with("[0]") {
+        name.slice_to(name.len() - 3)
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gl2::UNSIGNED_INT;
+    use mock;
+    use super::{strip_first_element, strip_subscript};
+
+    #[test]
+    fn strips_only_the_final_subscript() {
+        assert_eq!(strip_subscript("color"), "color");
+        assert_eq!(strip_subscript("lights[2]"), "lights");
+        assert_eq!(strip_subscript("lights[2].color"), "lights[2].color");
+        assert_eq!(strip_subscript("a[1].b[0]"), "a[1].b");
+        assert_eq!(strip_subscript("a[i]"), "a[i]");
+    }
+
+    #[test]
+    fn strips_the_first_element_suffix() {
+        assert_eq!(strip_first_element("lights[0]"), "lights");
+        assert_eq!(strip_first_element("a[1].b[0]"), "a[1].b");
+        assert_eq!(strip_first_element("lights[2].color"), "lights[2].color");
+        assert_eq!(strip_first_element("lights[1]"), "lights[1]");
+    }
+
+    #[test]
+    fn does_not_read_unknown_types() {
+        mock::reset();
+        let gl = mock::load();
+        assert_eq!(super::get_uniform_value(&gl, 1, 0, UNSIGNED_INT), None);
+        assert!(mock::calls().is_empty());
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Descriptions of interleaved vertex formats, usually produced by `#[derive(Vertex)]` from the
+//! `opengles_macros` crate:
+//!
+//! ```ignore
+//! #![feature(phase)]
+//! #[phase(plugin)]
+//! extern crate opengles_macros;
+//!
+//! #[repr(C)]
+//! #[derive(Vertex)]
+//! struct TexturedVertex {
+//!     position: [f32, ..3],
+//!     #[normalized]
+//!     color: [u8, ..4],
+//! }
+//!
+//! let layout = VertexLayout::of::<TexturedVertex>();
+//! layout.bind(gl, program).unwrap();
+//! ```
+
+use enums::AttribComponentType;
+use error::VertexAttribError;
+use gl2::{AttribComponent, Fixed, Gl, GLint, GLsizei, GLuint};
+
+/// One field of a vertex, fed to the attribute of the same name.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct VertexAttribute {
+    /// The attribute name, which is also the field name.
+    pub name: &'static str,
+    pub component_type: AttribComponentType,
+    /// The number of components, 1 to 4.
+    pub size: GLint,
+    /// Whether fixed-point components are mapped to [0, 1] or [-1, 1].
+    pub normalized: bool,
+    /// The byte offset of the field within the vertex.
+    pub offset: uint,
+}
+
+impl VertexAttribute {
+    /// Describes a field of type `T`.
+    pub fn new<T: VertexField>(name: &'static str, normalized: bool, offset: uint)
+                               -> VertexAttribute {
+        VertexAttribute {
+            name: name,
+            component_type: VertexField::component_type(None::<T>),
+            size: VertexField::size(None::<T>),
+            normalized: normalized,
+            offset: offset,
+        }
+    }
+}
+
+/// The attributes of an interleaved vertex format.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct VertexLayout {
+    /// The byte size of one vertex.
+    pub stride: uint,
+    pub attributes: Vec<VertexAttribute>,
+}
+
+impl VertexLayout {
+    pub fn of<V: Vertex>() -> VertexLayout {
+        Vertex::layout(None::<V>)
+    }
+
+    /// Points the attributes of `program` at the buffer bound to `ARRAY_BUFFER`, which must hold
+    /// vertices of this layout, and enables their arrays. Fields without an active attribute of
+    /// the same name are skipped. Stops at the first attribute `vertex_attrib_pointer` rejects.
+    pub fn bind(&self, gl: &Gl, program: GLuint) -> Result<(), VertexAttribError> {
+        let stride = self.stride as GLsizei;
+        for attribute in self.attributes.iter() {
+            let location = gl.get_attrib_location(program, attribute.name);
+            if location < 0 {
+                continue
+            }
+            let index = location as GLuint;
+            let size = attribute.size;
+            let normalized = attribute.normalized;
+            let offset = attribute.offset;
+            match attribute.component_type {
+                AttribComponentType::Byte => {
+                    try!(gl.vertex_attrib_pointer::<i8>(index, size, normalized, stride, offset))
+                }
+                AttribComponentType::UnsignedByte => {
+                    try!(gl.vertex_attrib_pointer::<u8>(index, size, normalized, stride, offset))
+                }
+                AttribComponentType::Short => {
+                    try!(gl.vertex_attrib_pointer::<i16>(index, size, normalized, stride, offset))
+                }
+                AttribComponentType::UnsignedShort => {
+                    try!(gl.vertex_attrib_pointer::<u16>(index, size, normalized, stride, offset))
+                }
+                AttribComponentType::Int => {
+                    try!(gl.vertex_attrib_pointer::<i32>(index, size, normalized, stride, offset))
+                }
+                AttribComponentType::Fixed => {
+                    try!(gl.vertex_attrib_pointer::<Fixed>(index, size, normalized, stride, offset))
+                }
+                AttribComponentType::Float => {
+                    try!(gl.vertex_attrib_pointer::<f32>(index, size, normalized, stride, offset))
+                }
+            }
+            gl.enable_vertex_attrib_array(index);
+        }
+        Ok(())
+    }
+}
+
+/// A struct whose fields are vertex attributes. Implement it with `#[derive(Vertex)]`.
+pub trait Vertex {
+    /// The argument is only used to select the impl.
+    fn layout(_: Option<Self>) -> VertexLayout;
+}
+
+/// A type that can be a field of a `Vertex`: an `AttribComponent` or an array of 2 to 4 of them.
+pub trait VertexField {
+    fn component_type(_: Option<Self>) -> AttribComponentType;
+    fn size(_: Option<Self>) -> GLint;
+}
+
+macro_rules! vertex_field {
+    ($($ty:ty => $component_type:ident),+) => {
+        $(impl VertexField for $ty {
+            fn component_type(_: Option<$ty>) -> AttribComponentType {
+                AttribComponentType::$component_type
+            }
+            fn size(_: Option<$ty>) -> GLint {
+                1
+            }
+        })+
+    }
+}
+
+vertex_field! {
+    i8 => Byte,
+    u8 => UnsignedByte,
+    i16 => Short,
+    u16 => UnsignedShort,
+    i32 => Int,
+    Fixed => Fixed,
+    f32 => Float
+}
+
+macro_rules! vertex_field_array {
+    ($($size:expr),+) => {
+        $(impl<T: AttribComponent + VertexField> VertexField for [T, ..$size] {
+            fn component_type(_: Option<[T, ..$size]>) -> AttribComponentType {
+                VertexField::component_type(None::<T>)
+            }
+            fn size(_: Option<[T, ..$size]>) -> GLint {
+                $size
+            }
+        })+
+    }
+}
+
+vertex_field_array! { 2, 3, 4 }
+
+#[cfg(test)]
+mod test {
+    use enums::AttribComponentType;
+    use mock;
+    use super::{VertexAttribute, VertexLayout};
+
+    #[test]
+    fn binds_with_the_layout_stride() {
+        mock::reset();
+        let gl = mock::load();
+        let layout = VertexLayout {
+            stride: 20,
+            attributes: vec![
+                VertexAttribute::new::<[f32, ..3]>("position", false, 0),
+                VertexAttribute::new::<[u8, ..4]>("color", true, 12),
+            ],
+        };
+        assert_eq!(layout.attributes[1].component_type, AttribComponentType::UnsignedByte);
+        assert_eq!(layout.attributes[1].size, 4);
+
+        mock::push_return("glGetAttribLocation", 0);
+        mock::push_return("glGetAttribLocation", 1);
+        assert_eq!(layout.bind(&gl, 1), Ok(()));
+        let calls = mock::calls();
+        let pointers: Vec<&mock::Call> = calls.iter()
+                                              .filter(|call| call.name == "glVertexAttribPointer")
+                                              .collect();
+        assert_eq!(pointers.len(), 2);
+        for call in pointers.iter() {
+            // The arguments are index, size, type, normalized, stride and offset.
+            assert_eq!(call.args[4].as_slice(), "20");
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2014 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Layouts generated by `#[derive(Vertex)]` from `opengles_macros`.
+
+#![feature(phase)]
+
+#[phase(plugin)]
+extern crate opengles_macros;
+extern crate opengles;
+
+use opengles::enums::AttribComponentType;
+use opengles::vertex::{VertexAttribute, VertexLayout};
+
+#[repr(C)]
+#[derive(Vertex)]
+struct TexturedVertex {
+    position: [f32, ..3],
+    #[normalized]
+    color: [u8, ..4],
+    tex_coord: [u16, ..2],
+}
+
+#[repr(C)]
+#[derive(Vertex)]
+struct PaddedVertex {
+    index: u8,
+    weight: f32,
+}
+
+#[test]
+fn interleaved_layout() {
+    let layout = VertexLayout::of::<TexturedVertex>();
+    assert_eq!(layout, VertexLayout {
+        stride: 20,
+        attributes: vec![
+            VertexAttribute::new::<[f32, ..3]>("position", false, 0),
+            VertexAttribute::new::<[u8, ..4]>("color", true, 12),
+            VertexAttribute::new::<[u16, ..2]>("tex_coord", false, 16),
+        ],
+    });
+    assert_eq!(layout.attributes[0].component_type, AttribComponentType::Float);
+    assert_eq!(layout.attributes[0].size, 3);
+}
+
+#[test]
+fn offsets_include_padding() {
+    let layout = VertexLayout::of::<PaddedVertex>();
+    assert_eq!(layout.stride, 8);
+    assert_eq!(layout.attributes.len(), 2);
+    assert_eq!(layout.attributes[0].offset, 0);
+    assert_eq!(layout.attributes[1].offset, 4);
+    assert_eq!(layout.attributes[1].component_type, AttribComponentType::Float);
+}